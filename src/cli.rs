@@ -1,58 +1,582 @@
 use anyhow::Result;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use colored::Colorize;
 use dialoguer::{Confirm, Input};
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
 
-use crate::config::ProjectConfig;
-use crate::validation::{check_and_install_pnpm, check_node_version};
+use crate::config::{
+    AuditLevel, Component, ComponentStyle, ConfigFormat, CssStructure, DeployTarget, Desktop,
+    EnvValidation, Formatter, Logger, Observability, PackageManager, Profile, ProjectConfig,
+    ReactVersion, ReadmeFeatures, ReadmeLang, ReadmeStyle, Robots, Styling, ToolVersions,
+};
+use crate::emoji::{say, set_emoji, terminal_text};
+use crate::generator::{MultiProjectReport, PhaseTiming, DEFAULT_JOBS};
+use crate::git::repo_name_from_url;
+use crate::openapi::is_remote_spec;
+use crate::validation::{
+    check_and_install_pnpm, check_node_version, check_package_manager, check_path_limits,
+    parse_node_version, slug_problem, ConfigReport, Warning, NPM_NAME_MAX_LENGTH,
+};
+use crate::versions::MIN_NODE_VERSION;
 
 #[derive(Parser)]
-#[command(name = "aui-next-gen")]
+#[command(name = "aui-next-gen", version)]
 #[command(about = "Generate Next.js projects with Tailwind & ESLint using pnpm")]
+#[command(args_conflicts_with_subcommands = true)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Project name (optional - will prompt if not provided)
     pub name: Option<String>,
 
+    /// More projects to generate with the same options, alongside the first
+    #[arg(value_name = "MORE_NAMES", conflicts_with_all = ["print_config", "dry_run_json", "name_from_git"])]
+    pub more_names: Vec<String>,
+
+    /// How many of several projects to scaffold at once; installs always run one at a time
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_JOBS)]
+    pub jobs: usize,
+
+    /// Longest project name to accept (npm allows up to 214 characters)
+    #[arg(long, value_name = "N", default_value_t = NPM_NAME_MAX_LENGTH)]
+    pub max_name_length: usize,
+
+    /// Use the repository name from a git URL as the project name
+    #[arg(long, value_name = "URL", conflicts_with = "name")]
+    pub name_from_git: Option<String>,
+
+    /// Refuse names that aren't already a lowercase slug (e.g. "My App")
+    #[arg(long)]
+    pub strict_name: bool,
+
+    /// Accept an empty answer at the name prompt as "my-app"
+    #[arg(long)]
+    pub allow_empty_name: bool,
+
     /// Skip dependency installation
     #[arg(long)]
     pub skip_install: bool,
+
+    /// Minimum Node.js version to require, as major.minor (defaults to 18.18)
+    #[arg(long, value_name = "MAJOR.MINOR", value_parser = parse_node_version)]
+    pub minimum_node: Option<(u32, u32)>,
+
+    /// Package manager used to install and run the project
+    #[arg(long, value_enum, default_value_t = PackageManager::Pnpm)]
+    pub package_manager: PackageManager,
+
+    /// Fail installs on peer dependency mismatches (.npmrc)
+    #[arg(long)]
+    pub strict_peers: bool,
+
+    /// Don't install missing peer dependencies automatically (.npmrc)
+    #[arg(long)]
+    pub no_auto_install_peers: bool,
+
+    /// Mark the regions of next.config.ts, postcss.config.mjs and .gitignore that upgrade may rewrite
+    #[arg(long)]
+    pub managed_markers: bool,
+
+    /// List package.json dependencies alphabetically for cleaner diffs
+    #[arg(long, alias = "dependencies-sorted")]
+    pub sort_deps: bool,
+
+    /// Treat a failed install or failed --verify-lint checks as an error
+    #[arg(long, conflicts_with_all = ["skip_install", "keep_going"])]
+    pub strict_install: bool,
+
+    /// Keep the lenient install even with --profile enterprise
+    #[arg(long, conflicts_with = "strict_install")]
+    pub no_strict_install: bool,
+
+    /// Kill the dependency install if it hasn't finished after SECS seconds
+    #[arg(long, value_name = "SECS", value_parser = clap::value_parser!(u64).range(1..))]
+    pub install_timeout: Option<u64>,
+
+    /// After installing, run the package manager's vulnerability audit and show its summary
+    #[arg(long, alias = "dependency-audit")]
+    pub audit: bool,
+
+    /// Fail when the audit finds vulnerabilities of this severity or higher
+    #[arg(long, value_enum, requires = "audit", value_name = "LEVEL")]
+    pub audit_level: Option<AuditLevel>,
+
+    /// Shell command to run in the new project after generating (and installing)
+    #[arg(long, value_name = "COMMAND", alias = "postgen-command")]
+    pub postgen: Option<String>,
+
+    /// After installing, run the linter and `tsc --noEmit` in the new project
+    #[arg(long, alias = "run-lint-after")]
+    pub verify_lint: bool,
+
+    /// Install dependencies without running their lifecycle scripts
+    #[arg(long, alias = "no-install-scripts")]
+    pub ignore_scripts: bool,
+
+    /// Install exactly what an existing lockfile records (pnpm/yarn/bun --frozen-lockfile, npm ci)
+    #[arg(long, alias = "clean-install")]
+    pub frozen_lockfile: bool,
+
+    /// Use Turbopack for development without prompting
+    #[arg(long)]
+    pub turbo: bool,
+
+    /// Add a generated public/logo.svg and show it on the home page via next/image
+    #[arg(long)]
+    pub demo_asset: bool,
+
+    /// Where the app will be deployed; shapes the README's deploy section and config files
+    #[arg(long, value_enum, default_value_t = DeployTarget::Vercel)]
+    pub deploy_target: DeployTarget,
+
+    /// Wrap the app in a desktop shell (switches Next.js to a static export)
+    #[arg(long, value_enum)]
+    pub desktop: Option<Desktop>,
+
+    /// Add a dynamic Open Graph image route at /api/og?title=...
+    #[arg(long)]
+    pub og_route: bool,
+
+    /// Add a multipart upload route at /api/upload and a form using it at /upload
+    #[arg(long)]
+    pub with_upload_example: bool,
+
+    /// Add a server action returning a typed ActionResult, and a form using it at /subscribe
+    #[arg(long)]
+    pub with_server_actions: bool,
+
+    /// Backend URL that a development-only catch-all route under /api forwards requests to
+    #[arg(long, value_name = "TARGET_URL")]
+    pub api_proxy: Option<String>,
+
+    /// Generate src/middleware.ts chaining handlers from src/middleware/ (security headers, rate limiting)
+    #[arg(long)]
+    pub with_middleware_chain: bool,
+
+    /// Leave out the middleware chain a --profile would add
+    #[arg(long, conflicts_with = "with_middleware_chain")]
+    pub no_middleware_chain: bool,
+
+    /// React major version, independent of Next.js (defaults to the one Next.js 15 expects)
+    #[arg(long, value_enum, value_name = "MAJOR", default_value_t = ReactVersion::V19)]
+    pub react_version: ReactVersion,
+
+    /// How the demo page and Button are styled
+    #[arg(long, value_enum, default_value_t = Styling::Tailwind)]
+    pub styling: Styling,
+
+    /// Keep global styles in globals.css or split out utilities.css and components.css
+    #[arg(long, value_enum, default_value_t = CssStructure::Single)]
+    pub css_structure: CssStructure,
+
+    /// Put Button in src/components/ui/ and re-export it from an index.ts barrel
+    #[arg(long)]
+    pub components_barrel: bool,
+
+    /// Extra UI primitives to generate next to Button (repeatable or comma-separated)
+    #[arg(
+        long,
+        value_enum,
+        value_delimiter = ',',
+        alias = "component",
+        value_name = "NAME"
+    )]
+    pub components: Vec<Component>,
+
+    /// Generate a mount test next to each --components primitive (needs --testing)
+    #[arg(long, alias = "scaffold-tests-for")]
+    pub scaffold_tests: bool,
+
+    /// Declare component props with React.FC (fc) or a typed function declaration (fn)
+    #[arg(long, value_enum, default_value_t = ComponentStyle::Fc, alias = "component-prop-style")]
+    pub component_style: ComponentStyle,
+
+    /// Add src/app/loading.tsx with a skeleton of the demo page's card grid
+    #[arg(long)]
+    pub with_skeletons: bool,
+
+    /// Add src/app/error.tsx with a Button retry; with --with-skeletons, build loading.tsx from a Skeleton component
+    #[arg(long)]
+    pub with_app_states: bool,
+
+    /// Add src/app/not-found.tsx, a styled 404 with a link back home
+    #[arg(long)]
+    pub not_found: bool,
+
+    /// Add useMediaQuery, useDebounce and useLocalStorage hooks to src/hooks
+    #[arg(long)]
+    pub common_hooks: bool,
+
+    /// Add postcss-nesting ahead of Tailwind for full CSS nesting support
+    #[arg(long)]
+    pub postcss_nesting: bool,
+
+    /// Report unresolved imports in ESLint, resolving @/ through tsconfig paths
+    #[arg(long)]
+    pub import_rules: bool,
+
+    /// Lint with ESLint (next lint) or lint and format with Biome
+    #[arg(long, value_enum, default_value_t = Formatter::EslintPrettier)]
+    pub formatter: Formatter,
+
+    /// Add Upstash rate limiting in src/libs/ratelimit.ts, applied to generated API routes
+    #[arg(long)]
+    pub ratelimit: bool,
+
+    /// Default React Query staleTime in milliseconds (defaults to 60000)
+    #[arg(long, value_name = "MS")]
+    pub query_stale_time: Option<u64>,
+
+    /// Default number of React Query retries for failed queries (defaults to 1)
+    #[arg(long, value_name = "N")]
+    pub query_retry: Option<u32>,
+
+    /// Default timeout of the generated apiRequest in milliseconds, 0 for none (defaults to 10000)
+    #[arg(long, value_name = "MS")]
+    pub api_timeout: Option<u64>,
+
+    /// Leave React Query Devtools out of the query provider and dependencies
+    #[arg(long, alias = "no-devtools")]
+    pub no_query_devtools: bool,
+
+    /// OpenAPI 3 spec (JSON/YAML path or URL) to generate typed API schemas from
+    #[arg(long, value_name = "SPEC", alias = "generate-types-from-openapi")]
+    pub openapi: Option<String>,
+
+    /// Directory whose contents (logo, favicon...) are copied into public/; existing files are kept
+    #[arg(long, value_name = "DIR")]
+    pub assets_from: Option<String>,
+
+    /// Scaffold directory to leave out, e.g. src/fonts (repeatable)
+    #[arg(long = "skip-directory", value_name = "DIR")]
+    pub skip_directories: Vec<String>,
+
+    /// Logger to generate in src/libs/logger.ts, used by the generated API routes
+    #[arg(long, value_enum, default_value_t = Logger::None)]
+    pub logger: Logger,
+
+    /// Instrumentation to generate in src/instrumentation.ts
+    #[arg(long, value_enum, default_value_t = Observability::None)]
+    pub observability: Observability,
+
+    /// Add src/app/sitemap.ts and robots.ts listing the generated pages
+    #[arg(long)]
+    pub seo: bool,
+
+    /// How --seo serves robots rules: src/app/robots.ts or public/robots.txt [default: dynamic]
+    #[arg(long, value_enum)]
+    pub robots: Option<Robots>,
+
+    /// Add src/app/manifest.ts (name, --theme-color and icons) for installable PWAs
+    #[arg(long)]
+    pub manifest: bool,
+
+    /// Show the name in Title Case ("My Cool App") in the page title and heading
+    #[arg(long, alias = "app-name-in-title-case")]
+    pub title_case: bool,
+
+    /// README to generate
+    #[arg(long, value_enum, default_value_t = ReadmeStyle::Full)]
+    pub readme: ReadmeStyle,
+
+    /// How the full README presents features: a bullet list or a tech stack table
+    #[arg(long, value_enum, default_value_t = ReadmeFeatures::List, alias = "description-badge")]
+    pub readme_features: ReadmeFeatures,
+
+    /// Language of the README's headings and prose
+    #[arg(long, value_enum, default_value_t = ReadmeLang::En)]
+    pub readme_lang: ReadmeLang,
+
+    /// Plain text instead of emoji in the README and the printed messages
+    #[arg(long, alias = "no-readme-emoji")]
+    pub no_emoji: bool,
+
+    /// Add Prisma with a starter schema and a shared client in src/libs/db.ts
+    #[arg(long)]
+    pub prisma: bool,
+
+    /// Generate src/env.ts that validates environment variables
+    #[arg(long, value_enum, default_value_t = EnvValidation::None)]
+    pub env_validation: EnvValidation,
+
+    /// Path or package of a shared tsconfig for the generated tsconfig.json to extend
+    #[arg(long, value_name = "PATH")]
+    pub tsconfig_extends: Option<String>,
+
+    /// With --tsconfig-extends, keep the shared compiler options as local overrides
+    #[arg(long)]
+    pub tsconfig_keep_options: bool,
+
+    /// Drop `incremental` from tsconfig.json so no .tsbuildinfo files are written
+    #[arg(long)]
+    pub no_incremental: bool,
+
+    /// Turn on a curated set of options: personal (minimal), team (husky, Vitest, CI) or enterprise (team, security headers, Dependabot, CODEOWNERS, strict install)
+    #[arg(long, value_enum)]
+    pub profile: Option<Profile>,
+
+    /// Add Vitest with example component and API tests
+    #[arg(long)]
+    pub testing: bool,
+
+    /// Leave out the Vitest setup a --profile would add
+    #[arg(long, conflicts_with = "testing")]
+    pub no_testing: bool,
+
+    /// Add husky with a pre-commit hook that runs the linter
+    #[arg(long)]
+    pub husky: bool,
+
+    /// Leave out the husky hooks a --profile would add
+    #[arg(long, conflicts_with = "husky")]
+    pub no_husky: bool,
+
+    /// Add commitlint with a Conventional Commits commit-msg hook (requires --husky)
+    #[arg(long)]
+    pub commitlint: bool,
+
+    /// Compile with Babel (.babelrc with next/babel) instead of SWC; slower builds, no next/font
+    #[arg(long, alias = "babel-config")]
+    pub use_babel: bool,
+
+    /// Download Inter into src/fonts and load it with next/font/local instead of fetching from Google at build time
+    #[arg(long)]
+    pub fonts_local: bool,
+
+    /// Version manager file pinning Node (and pnpm): .mise.toml or .tool-versions
+    #[arg(long, value_enum, default_value_t = ToolVersions::None)]
+    pub tool_versions: ToolVersions,
+
+    /// Add .vscode/tasks.json with dev, build, lint and test tasks, and Tailwind CSS data
+    #[arg(long)]
+    pub vscode: bool,
+
+    /// Add .devcontainer/devcontainer.json for Codespaces and remote development
+    #[arg(long)]
+    pub devcontainer: bool,
+
+    /// Add .github/dependabot.yml with weekly updates, minor and patch grouped
+    #[arg(long)]
+    pub dependabot: bool,
+
+    /// Leave out the Dependabot config a --profile would add
+    #[arg(long, conflicts_with = "dependabot")]
+    pub no_dependabot: bool,
+
+    /// Add a GitHub Actions workflow that installs, lints, tests and builds on pushes and pull requests
+    #[arg(long)]
+    pub ci: bool,
+
+    /// Leave out the CI workflow a --profile would add
+    #[arg(long, conflicts_with = "ci")]
+    pub no_ci: bool,
+
+    /// Add .github/CODEOWNERS, owned by the --git-remote account when one is given
+    #[arg(long)]
+    pub codeowners: bool,
+
+    /// Leave out the CODEOWNERS file a --profile would add
+    #[arg(long, conflicts_with = "codeowners")]
+    pub no_codeowners: bool,
+
+    /// Add a .watchmanconfig ignoring node_modules and .next, for teams whose tools use watchman
+    #[arg(long)]
+    pub watchman: bool,
+
+    /// Dev server port (defaults to 3000)
+    #[arg(long)]
+    pub port: Option<u16>,
+
+    /// Initial package.json version, for a package continuing existing versioning (defaults to 0.1.0)
+    #[arg(long = "pkg-version", value_name = "SEMVER")]
+    pub package_version: Option<String>,
+
+    /// Make package.json publishable: not private, exporting the components built to dist/
+    #[arg(long)]
+    pub publishable: bool,
+
+    /// Hex color for the browser UI (the layout's viewport themeColor)
+    #[arg(long, value_name = "HEX")]
+    pub theme_color: Option<String>,
+
+    /// Start the dev server and open the app in the browser after installing
+    #[arg(long)]
+    pub open: bool,
+
+    /// Initialize a git repository with an initial commit
+    #[arg(long)]
+    pub git: bool,
+
+    /// Append a JSON line with the enabled options to this local file (nothing is sent anywhere)
+    #[arg(long, value_name = "PATH")]
+    pub usage_log: Option<PathBuf>,
+
+    /// Add this URL as the `origin` remote after the initial commit (requires --git)
+    #[arg(long, value_name = "URL")]
+    pub git_remote: Option<String>,
+
+    /// Branch the initial commit lands on (requires --git) [default: main]
+    #[arg(long, value_name = "NAME")]
+    pub git_branch: Option<String>,
+
+    /// Generate even when the output path looks like an accident (/, $HOME, a top-level directory)
+    #[arg(long)]
+    pub force: bool,
+
+    /// Print the resolved configuration as TOML and exit without generating anything
+    #[arg(long)]
+    pub print_config: bool,
+
+    /// Generate from a config file as written by --print-config (.toml, .json, .yaml or .yml); the name and any flags given alongside it override the file
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Format for --print-config
+    #[arg(long, value_enum, default_value_t = ConfigFormat::Toml, requires = "print_config")]
+    pub config_format: ConfigFormat,
+
+    /// Print every directory and file that would be generated, with its content, as JSON and exit
+    #[arg(long, conflicts_with = "print_config")]
+    pub dry_run_json: bool,
+
+    /// Finish the scaffold when git is missing or the install fails, reporting it as a warning
+    #[arg(long)]
+    pub keep_going: bool,
+
+    /// Generate into an existing directory, adding only new files; refused if any generated file is already there
+    #[arg(long)]
+    pub additive: bool,
+
+    /// Print how long each phase (checks, directories, files, git, install...) took
+    #[arg(long)]
+    pub trace: bool,
+
+    /// Never use colors in the output
+    #[arg(long, conflicts_with = "force_color")]
+    pub no_color: bool,
+
+    /// Always use colors, even when the output isn't a terminal (e.g. CI logs)
+    #[arg(long)]
+    pub force_color: bool,
+
+    /// Commit the scaffold in logical stages, one commit per feature (requires --git)
+    #[arg(long, alias = "commit-per-feature")]
+    pub granular_commits: bool,
 }
 
-pub fn get_project_config(args: Cli) -> Result<ProjectConfig> {
-    println!("{}", "🚀 AUI Next.js Generator".bold().blue());
-    println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".blue());
+#[derive(Subcommand)]
+pub enum Command {
+    /// Refresh config files (tsconfig, ESLint, Next.js, PostCSS, .gitignore) of an existing project
+    Upgrade {
+        /// Project directory (defaults to the current directory)
+        path: Option<PathBuf>,
 
-    let project_name = match args.name {
-        Some(name) => {
-            println!("{}", name.green());
-            name
+        /// Print a unified diff for each file that would change and, in a terminal, ask before applying it
+        #[arg(long)]
+        show_diff: bool,
+    },
+    /// Check Node.js, the package manager, disk space and write access without generating anything
+    Check {
+        /// Package manager to look for
+        #[arg(long, value_enum, default_value_t = PackageManager::Pnpm)]
+        package_manager: PackageManager,
+
+        /// Minimum Node.js version to require, as major.minor (defaults to 18.18)
+        #[arg(long, value_name = "MAJOR.MINOR", value_parser = parse_node_version)]
+        minimum_node: Option<(u32, u32)>,
+    },
+    /// Check a config file (as written by --print-config) without generating anything
+    Validate {
+        /// Config file to check: .toml, .json, .yaml or .yml
+        config: PathBuf,
+    },
+}
+
+/// Overrides colored's terminal detection when `--no-color` or
+/// `--force-color` is given, and drops emoji for `--no-emoji`.
+pub fn apply_color_choice(args: &Cli) {
+    set_emoji(!args.no_emoji);
+    if args.force_color {
+        colored::control::set_override(true);
+    } else if args.no_color {
+        colored::control::set_override(false);
+    }
+}
+
+pub fn get_project_config(args: &Cli) -> Result<ProjectConfig> {
+    // Keep stdout to the config (or JSON plan) alone for --print-config and --dry-run-json
+    let quiet = args.print_config || args.dry_run_json;
+    if !quiet {
+        say!("{}", "🚀 AUI Next.js Generator".bold().blue());
+        println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".blue());
+    }
+
+    let name = match &args.name_from_git {
+        Some(url) => Some(repo_name_from_url(url)?),
+        None => args.name.clone(),
+    };
+
+    // The file takes the place of the prompts; flags still apply on top of it
+    if let Some(path) = &args.config {
+        let mut config = layer_cli_options(ProjectConfig::from_file(path)?, args)?;
+        if let Some(name) = name {
+            config.name = name;
         }
-        None => {
-            let name: String = Input::new()
-                .with_prompt("📝 Enter project name")
-                .interact()?;
+        if config.install_deps {
+            check_node_version(args.minimum_node.unwrap_or(MIN_NODE_VERSION))?;
+            if config.package_manager == PackageManager::Pnpm {
+                check_and_install_pnpm()?;
+            } else {
+                check_package_manager(config.package_manager)?;
+            }
+        }
+        return Ok(config);
+    }
 
-            if name.trim().is_empty() {
-                anyhow::bail!("Project name cannot be empty");
+    let project_name = match name {
+        Some(name) => {
+            if !quiet {
+                println!("{}", name.green());
             }
             name
         }
+        None => {
+            let answers = std::iter::repeat_with(|| {
+                Input::<String>::new()
+                    .with_prompt(terminal_text("📝 Enter project name"))
+                    .allow_empty(true)
+                    .interact_text()
+                    .map_err(Into::into)
+            });
+            prompt_project_name(answers, args.allow_empty_name, |name| {
+                name_problem(name, args.max_name_length, Path::new("."))
+                    .or_else(|| args.strict_name.then(|| slug_problem(name)).flatten())
+            })?
+        }
     };
 
     let (install_deps, use_turbo, use_react_query) = if args.skip_install {
         (false, false, false)
     } else {
-        check_node_version()?;
-        check_and_install_pnpm()?;
+        check_node_version(args.minimum_node.unwrap_or(MIN_NODE_VERSION))?;
+        if args.package_manager == PackageManager::Pnpm {
+            check_and_install_pnpm()?;
+        } else {
+            check_package_manager(args.package_manager)?;
+        }
 
         let install = Confirm::new()
-            .with_prompt("📦 Install project dependencies")
+            .with_prompt(terminal_text("📦 Install project dependencies"))
             .default(true)
             .interact()?;
 
-        let turbo = if install {
+        let turbo = if args.turbo {
+            true
+        } else if install {
             Confirm::new()
-                .with_prompt("🚀 Use Turbopack for faster development")
+                .with_prompt(terminal_text("🚀 Use Turbopack for faster development"))
                 .default(true)
                 .interact()?
         } else {
@@ -60,41 +584,496 @@ pub fn get_project_config(args: Cli) -> Result<ProjectConfig> {
         };
 
         let use_react_query = Confirm::new()
-            .with_prompt("🔄 Add React Query (TanStack Query) for data fetching")
+            .with_prompt(terminal_text(
+                "🔄 Add React Query (TanStack Query) for data fetching",
+            ))
             .default(true)
             .interact()?;
 
         (install, turbo, use_react_query)
     };
 
-    Ok(ProjectConfig::new(
-        project_name,
-        install_deps,
-        use_turbo,
-        use_react_query,
-    ))
+    let mut config = ProjectConfig::new(project_name, install_deps, use_turbo, use_react_query);
+    apply_cli_options(&mut config, args);
+
+    Ok(config)
+}
+
+/// Name an empty answer stands for with `--allow-empty-name`.
+pub const DEFAULT_PROJECT_NAME: &str = "my-app";
+
+/// Answers the name prompt accepts before giving up.
+pub const NAME_PROMPT_ATTEMPTS: usize = 3;
+
+/// Why `name` can't be used for a new project in `base_dir`, if anything.
+pub fn name_problem(name: &str, max_length: usize, base_dir: &Path) -> Option<String> {
+    if name.is_empty() {
+        return Some("Project name cannot be empty".to_string());
+    }
+    if name.len() > max_length {
+        return Some(format!(
+            "Project name is {} characters; the limit is {} (--max-name-length)",
+            name.len(),
+            max_length
+        ));
+    }
+    let path = base_dir.join(name);
+    if path.exists() {
+        return Some(format!("Directory '{}' already exists", name));
+    }
+    check_path_limits(&path).err().map(|err| err.to_string())
+}
+
+/// Takes names from `answers` until `problem` accepts one, explaining each
+/// rejection. Empty answers become [`DEFAULT_PROJECT_NAME`] when
+/// `allow_empty` is set.
+pub fn prompt_project_name(
+    answers: impl IntoIterator<Item = Result<String>>,
+    allow_empty: bool,
+    problem: impl Fn(&str) -> Option<String>,
+) -> Result<String> {
+    for answer in answers.into_iter().take(NAME_PROMPT_ATTEMPTS) {
+        let mut name = answer?.trim().to_string();
+        if name.is_empty() && allow_empty {
+            name = DEFAULT_PROJECT_NAME.to_string();
+        }
+        match problem(&name) {
+            None => return Ok(name),
+            Some(problem) => say!("{}", format!("❌ {}; try again", problem).red()),
+        }
+    }
+    anyhow::bail!(
+        "No usable project name after {} attempts",
+        NAME_PROMPT_ATTEMPTS
+    )
+}
+
+/// Copies the non-interactive flags from `args` onto `config`.
+pub fn apply_cli_options(config: &mut ProjectConfig, args: &Cli) {
+    config.init_git = args.git;
+    config.granular_commits = args.granular_commits;
+    config.demo_asset = args.demo_asset;
+    config.use_husky = args.husky;
+    config.use_commitlint = args.commitlint;
+    config.port = args.port;
+    config.theme_color = args.theme_color.clone();
+    config.package_version = args.package_version.clone();
+    config.publishable = args.publishable;
+    config.open_dev_server = args.open;
+    config.use_og_route = args.og_route;
+    config.with_upload_example = args.with_upload_example;
+    config.server_actions = args.with_server_actions;
+    config.api_proxy = args.api_proxy.clone();
+    config.middleware_chain = args.with_middleware_chain;
+    config.readme = args.readme;
+    config.env_validation = args.env_validation;
+    config.tsconfig_extends = args.tsconfig_extends.clone();
+    config.tsconfig_keep_options = args.tsconfig_keep_options;
+    config.incremental = !args.no_incremental;
+    config.use_testing = args.testing;
+    config.styling = args.styling;
+    config.react_version = args.react_version;
+    config.css_structure = args.css_structure;
+    config.with_skeletons = args.with_skeletons;
+    config.app_states = args.with_app_states;
+    config.not_found = args.not_found;
+    config.common_hooks = args.common_hooks;
+    config.postcss_nesting = args.postcss_nesting;
+    config.import_rules = args.import_rules;
+    config.formatter = args.formatter;
+    config.package_manager = args.package_manager;
+    config.strict_peers = args.strict_peers;
+    config.sort_deps = args.sort_deps;
+    config.managed_markers = args.managed_markers;
+    config.auto_install_peers = !args.no_auto_install_peers;
+    config.use_prisma = args.prisma;
+    config.readme_features = args.readme_features;
+    config.readme_lang = args.readme_lang;
+    config.emoji = !args.no_emoji;
+    config.desktop = args.desktop;
+    config.git_remote = args.git_remote.clone();
+    config.git_branch = args.git_branch.clone();
+    config.components_barrel = args.components_barrel;
+    config.components = args.components.clone();
+    config.components.sort();
+    config.components.dedup();
+    config.component_style = args.component_style;
+    config.scaffold_tests = args.scaffold_tests;
+    config.deploy_target = args.deploy_target;
+    config.ignore_scripts = args.ignore_scripts;
+    config.frozen_lockfile = args.frozen_lockfile;
+    config.use_ratelimit = args.ratelimit;
+    config.use_seo = args.seo;
+    config.manifest = args.manifest;
+    config.robots = args.robots.unwrap_or_default();
+    config.keep_going = args.keep_going;
+    config.trace = args.trace;
+    config.additive = args.additive;
+    config.force = args.force;
+    config.vscode = args.vscode;
+    config.devcontainer = args.devcontainer;
+    config.dependabot = args.dependabot;
+    config.ci = args.ci;
+    config.codeowners = args.codeowners;
+    config.watchman = args.watchman;
+    config.use_babel = args.use_babel;
+    config.fonts_local = args.fonts_local;
+    config.query_stale_time = args.query_stale_time;
+    config.api_timeout = args.api_timeout;
+    config.query_retry = args.query_retry;
+    config.query_devtools = !args.no_query_devtools;
+    config.openapi = args.openapi.clone();
+    config.assets_from = args.assets_from.clone();
+    config.skip_directories = args
+        .skip_directories
+        .iter()
+        .map(|dir| dir.trim_end_matches('/').to_string())
+        .collect();
+    config.strict_install = args.strict_install;
+    config.install_timeout = args.install_timeout;
+    config.audit = args.audit;
+    config.audit_level = args.audit_level;
+    config.postgen = args.postgen.clone();
+    config.verify_lint = args.verify_lint;
+    config.logger = args.logger;
+    config.observability = args.observability;
+    config.tool_versions = args.tool_versions;
+    config.title_case = args.title_case;
+
+    if let Some(profile) = args.profile {
+        profile.apply(config);
+    }
+    apply_overrides(config, args);
+}
+
+/// Explicit `--no-*` flags, which win over a profile and a config file.
+fn apply_overrides(config: &mut ProjectConfig, args: &Cli) {
+    config.use_husky &= !args.no_husky;
+    config.use_testing &= !args.no_testing;
+    config.dependabot &= !args.no_dependabot;
+    config.ci &= !args.no_ci;
+    config.codeowners &= !args.no_codeowners;
+    config.middleware_chain &= !args.no_middleware_chain;
+    config.strict_install &= !args.no_strict_install;
+}
+
+/// `config`, loaded from a `--config` file, with the options `args` sets on
+/// top. An option counts as set when the flags move it off its default, so
+/// e.g. `--force` or `--postgen` apply while unset flags keep the file's
+/// values.
+fn layer_cli_options(config: ProjectConfig, args: &Cli) -> Result<ProjectConfig> {
+    fn from_flags(args: &Cli) -> Result<serde_json::Value> {
+        let install = !args.skip_install;
+        let mut config = ProjectConfig::new(String::new(), install, args.turbo && install, false);
+        apply_cli_options(&mut config, args);
+        Ok(serde_json::to_value(config)?)
+    }
+
+    let defaults = from_flags(&Cli::parse_from(["aui-next-gen"]))?;
+    let mut layered = serde_json::to_value(config)?;
+    if let serde_json::Value::Object(flags) = from_flags(args)? {
+        for (key, value) in flags {
+            if defaults.get(&key) != Some(&value) {
+                layered[key] = value;
+            }
+        }
+    }
+
+    let mut config: ProjectConfig = serde_json::from_value(layered)?;
+    // Never part of a config file, so only the flag can set it
+    config.postgen = args.postgen.clone();
+    apply_overrides(&mut config, args);
+    Ok(config)
 }
 
 pub fn print_success_message(config: &ProjectConfig) {
-    println!("\n{}", "🎉 Project created successfully!".green().bold());
+    say!("\n{}", "🎉 Project created successfully!".green().bold());
     println!("{}", "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━".green());
 
     // Show what was included
-    println!("✨ Included features:");
+    say!("✨ Included features:");
     println!("   • Next.js 15 with App Router");
     println!("   • Tailwind CSS v4 & TypeScript");
-    if config.use_turbo {
-        println!("   • Turbopack for faster development");
+    for feature in config.optional_features() {
+        println!("   • {}", feature);
     }
-    if config.use_react_query {
-        println!("   • React Query (TanStack Query)");
+
+    if config.install_deps && config.ignore_scripts {
+        say!(
+            "\n{}",
+            "⚠️  Install scripts were skipped (--ignore-scripts); run the setup steps below yourself"
+                .yellow()
+        );
     }
 
-    println!("\n📋 Next steps:");
+    say!("\n📋 Next steps:");
     println!("   cd {}", config.name.blue());
+    for step in next_steps(config) {
+        println!("   {}", step);
+    }
+    say!("\n🌐 Then open {}", config.dev_url());
+}
+
+/// Prints the `--trace` table of how long each generation phase took.
+pub fn print_phase_timings(name: &str, phases: &[PhaseTiming]) {
+    say!("\n⏱️  Phase timings for {}", name);
+    for timing in phases {
+        println!(
+            "   {:<12} {:>8.3}s",
+            timing.phase,
+            timing.elapsed.as_secs_f64()
+        );
+    }
+}
+
+/// Prints which of several projects were created, and why the others failed.
+pub fn print_multi_project_report(report: &MultiProjectReport) {
+    let failed = report.failures().count();
+    let heading = format!(
+        "🎉 Created {} of {} projects",
+        report.projects.len() - failed,
+        report.projects.len()
+    );
+    if failed == 0 {
+        say!("\n{}", heading.green().bold());
+    } else {
+        say!("\n{}", heading.yellow().bold());
+    }
+
+    for project in &report.projects {
+        match &project.result {
+            Ok(_) => println!("   • {}", project.name.blue()),
+            Err(err) => println!("   • {}", format!("{}: {}", project.name, err).red()),
+        }
+    }
+}
+
+/// The warnings of several projects, each given once: as-is when every
+/// created project had it, otherwise prefixed with the projects that did.
+pub fn multi_project_warnings(report: &MultiProjectReport) -> Vec<Warning> {
+    let mut messages: Vec<(String, Vec<&str>)> = Vec::new();
+    for project in &report.projects {
+        let Ok(generated) = &project.result else {
+            continue;
+        };
+        for warning in &generated.warnings {
+            match messages
+                .iter_mut()
+                .find(|(message, _)| *message == warning.message)
+            {
+                Some((_, names)) => names.push(&project.name),
+                None => messages.push((warning.message.clone(), vec![&project.name])),
+            }
+        }
+    }
+
+    let created = report.projects.len() - report.failures().count();
+    messages
+        .into_iter()
+        .map(|(message, names)| {
+            if names.len() == created {
+                Warning::new(message)
+            } else {
+                Warning::new(format!("{}: {}", names.join(", "), message))
+            }
+        })
+        .collect()
+}
+
+/// Prints the warnings collected during the run as one summary.
+pub fn print_warnings(warnings: &[Warning]) {
+    if warnings.is_empty() {
+        return;
+    }
+
+    let heading = match warnings.len() {
+        1 => "⚠️  1 warning".to_string(),
+        count => format!("⚠️  {} warnings", count),
+    };
+    say!("\n{}", heading.yellow().bold());
+    for warning in warnings {
+        println!("   • {}", warning.to_string().yellow());
+    }
+}
+
+/// Prints the diff `upgrade --show-diff` would apply to `path`, colored by
+/// line, and asks whether to apply it. Without a terminal to ask on, the
+/// change is applied.
+pub fn review_config_change(path: &str, diff: &str) -> Result<bool> {
+    println!();
+    for line in diff.lines() {
+        if line.starts_with("+++") || line.starts_with("---") {
+            println!("{}", line.bold());
+        } else if line.starts_with('+') {
+            println!("{}", line.green());
+        } else if line.starts_with('-') {
+            println!("{}", line.red());
+        } else if line.starts_with("@@") {
+            println!("{}", line.cyan());
+        } else {
+            println!("{}", line);
+        }
+    }
+
+    if !std::io::stdin().is_terminal() {
+        return Ok(true);
+    }
+    let apply = Confirm::new()
+        .with_prompt(format!("Apply changes to {}", path))
+        .default(true)
+        .interact()?;
+    Ok(apply)
+}
+
+/// Prints the result of `aui-next-gen validate` for the file at `path`.
+pub fn print_config_report(path: &std::path::Path, report: &ConfigReport) {
+    if report.is_valid() {
+        say!(
+            "{}",
+            format!("✅ {} is a valid config", path.display()).green()
+        );
+    } else {
+        let heading = match report.errors.len() {
+            1 => "❌ 1 error".to_string(),
+            count => format!("❌ {} errors", count),
+        };
+        say!("{}", heading.red().bold());
+        for error in &report.errors {
+            println!("   • {}", error.red());
+        }
+    }
+    print_warnings(&report.warnings);
+}
+
+/// Commands to run inside the new project, in order.
+pub fn next_steps(config: &ProjectConfig) -> Vec<String> {
+    let package_manager = config.package_manager;
+    let mut steps = Vec::new();
     if !config.install_deps {
-        println!("   pnpm install");
+        steps.push(format!("{} install", package_manager.command()));
+    } else if config.ignore_scripts {
+        // What the skipped postinstall and prepare scripts would have done
+        if config.use_prisma {
+            steps.push(package_manager.exec("prisma generate"));
+        }
+        if config.use_husky {
+            steps.push(package_manager.run_script("prepare"));
+        }
+    }
+    if config.openapi.as_deref().is_some_and(is_remote_spec) {
+        // Types for a URL spec are only fetched by the script
+        steps.push(package_manager.run_script("api:types"));
+    }
+    if config.init_git && config.git_remote.is_some() {
+        steps.push(format!("git push -u origin {}", config.initial_branch()));
+    }
+    steps.push(package_manager.run_script("dev"));
+    steps
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn answers(names: &[&str]) -> Vec<Result<String>> {
+        names.iter().map(|name| Ok(name.to_string())).collect()
+    }
+
+    #[test]
+    fn test_prompt_project_name_recovers_from_empty_and_taken_names() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp_dir.path().join("taken")).unwrap();
+        let problem = |name: &str| name_problem(name, NPM_NAME_MAX_LENGTH, temp_dir.path());
+
+        let name = prompt_project_name(answers(&["", "  ", "my-app"]), false, problem).unwrap();
+        assert_eq!(name, "my-app");
+
+        let name = prompt_project_name(answers(&["taken", " fresh "]), false, problem).unwrap();
+        assert_eq!(name, "fresh");
+    }
+
+    #[test]
+    fn test_prompt_project_name_gives_up_after_repeated_empties() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let problem = |name: &str| name_problem(name, NPM_NAME_MAX_LENGTH, temp_dir.path());
+
+        let err =
+            prompt_project_name(answers(&["", "", "", "late-app"]), false, problem).unwrap_err();
+        assert!(err.to_string().contains("after 3 attempts"));
+
+        let name = prompt_project_name(answers(&[""]), true, problem).unwrap();
+        assert_eq!(name, DEFAULT_PROJECT_NAME);
+    }
+
+    #[test]
+    fn test_config_file_in_any_format_matches_the_flags() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let flags = Cli::parse_from([
+            "aui-next-gen",
+            "file-app",
+            "--skip-install",
+            "--package-manager",
+            "bun",
+            "--seo",
+            "--husky",
+            "--components",
+            "spinner",
+        ]);
+        let expected = get_project_config(&flags).unwrap();
+
+        for (file, format) in [
+            ("aui.config.toml", ConfigFormat::Toml),
+            ("aui.config.json", ConfigFormat::Json),
+            ("aui.config.yml", ConfigFormat::Yaml),
+        ] {
+            let path = temp_dir.path().join(file);
+            std::fs::write(&path, expected.to_format(format).unwrap()).unwrap();
+            let path = path.to_str().unwrap();
+
+            let args = Cli::parse_from(["aui-next-gen", "--config", path]);
+            assert_eq!(get_project_config(&args).unwrap(), expected, "{}", file);
+
+            let args = Cli::parse_from(["aui-next-gen", "renamed-app", "--config", path]);
+            assert_eq!(get_project_config(&args).unwrap().name, "renamed-app");
+        }
+
+        let path = temp_dir.path().join("aui.config.ini");
+        std::fs::write(&path, expected.to_toml().unwrap()).unwrap();
+        let args = Cli::parse_from(["aui-next-gen", "--config", path.to_str().unwrap()]);
+        assert!(get_project_config(&args).is_err());
+    }
+
+    #[test]
+    fn test_flags_override_the_config_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let saved = ProjectConfig::builder("file-app")
+            .install_deps(false)
+            .package_manager(PackageManager::Bun)
+            .husky(true)
+            .seo(true)
+            .build();
+        let path = temp_dir.path().join("aui.config.yaml");
+        std::fs::write(&path, saved.to_format(ConfigFormat::Yaml).unwrap()).unwrap();
+
+        let args = Cli::parse_from([
+            "aui-next-gen",
+            "--config",
+            path.to_str().unwrap(),
+            "--force",
+            "--keep-going",
+            "--trace",
+            "--postgen",
+            "echo done",
+            "--no-husky",
+        ]);
+        let config = get_project_config(&args).unwrap();
+
+        assert!(config.force && config.keep_going && config.trace);
+        assert_eq!(config.postgen.as_deref(), Some("echo done"));
+        assert!(!config.use_husky);
+        // What the flags leave alone comes from the file
+        assert_eq!(config.package_manager, PackageManager::Bun);
+        assert!(config.use_seo);
+        assert!(!config.install_deps);
     }
-    println!("   pnpm dev");
-    println!("\n🌐 Then open http://localhost:3000");
 }
@@ -1,19 +1,1289 @@
-#[derive(Debug, Clone, PartialEq)]
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const DEFAULT_DEV_PORT: u16 = 3000;
+pub const DEFAULT_GIT_BRANCH: &str = "main";
+pub const DEFAULT_PACKAGE_VERSION: &str = "0.1.0";
+
+/// File format of a config printed by `--print-config` or checked by
+/// `validate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ConfigFormat {
+    #[default]
+    Toml,
+    Json,
+    Yaml,
+}
+
+impl ConfigFormat {
+    /// The format of the config file at `path`, going by its extension.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("toml") => Ok(ConfigFormat::Toml),
+            Some("json") => Ok(ConfigFormat::Json),
+            Some("yaml" | "yml") => Ok(ConfigFormat::Yaml),
+            _ => anyhow::bail!(
+                "Can't tell the format of '{}': use a .toml, .json, .yaml or .yml config",
+                path.display()
+            ),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ConfigFormat::Toml => "TOML",
+            ConfigFormat::Json => "JSON",
+            ConfigFormat::Yaml => "YAML",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EnvValidation {
+    /// No runtime validation of environment variables
+    #[default]
+    None,
+    /// A small zod schema parsed in src/env.ts
+    Zod,
+    /// @t3-oss/env-nextjs with separate server and client schemas
+    T3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReadmeFeatures {
+    /// Bulleted feature list
+    #[default]
+    List,
+    /// Table of the enabled technologies and their versions
+    TechStack,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum PackageManager {
+    #[default]
+    Pnpm,
+    Npm,
+    Yarn,
+    Bun,
+}
+
+impl PackageManager {
+    pub fn command(self) -> &'static str {
+        match self {
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Bun => "bun",
+        }
+    }
+
+    /// Lockfile the package manager writes next to package.json.
+    pub fn lockfile(self) -> &'static str {
+        match self {
+            PackageManager::Pnpm => "pnpm-lock.yaml",
+            PackageManager::Npm => "package-lock.json",
+            PackageManager::Yarn => "yarn.lock",
+            PackageManager::Bun => "bun.lockb",
+        }
+    }
+
+    /// README feature line for this package manager.
+    pub fn feature_label(self) -> &'static str {
+        match self {
+            PackageManager::Pnpm => "pnpm for fast package management",
+            PackageManager::Npm => "npm for package management",
+            PackageManager::Yarn => "Yarn for package management",
+            PackageManager::Bun => "Bun for fast package management",
+        }
+    }
+
+    /// Arguments that run a package.json script, e.g. `run lint` for npm.
+    pub fn script_args(self, script: &str) -> Vec<String> {
+        match self {
+            PackageManager::Pnpm | PackageManager::Yarn => vec![script.to_string()],
+            PackageManager::Npm | PackageManager::Bun => {
+                vec!["run".to_string(), script.to_string()]
+            }
+        }
+    }
+
+    /// Shell command that runs a package.json script, e.g. `npm run lint`.
+    pub fn run_script(self, script: &str) -> String {
+        format!("{} {}", self.command(), self.script_args(script).join(" "))
+    }
+
+    /// Shell command that runs a locally installed binary.
+    pub fn exec(self, binary: &str) -> String {
+        match self {
+            PackageManager::Pnpm => format!("pnpm exec {}", binary),
+            PackageManager::Npm => format!("npx --no -- {}", binary),
+            PackageManager::Yarn => format!("yarn {}", binary),
+            PackageManager::Bun => format!("bunx {}", binary),
+        }
+    }
+
+    /// Arguments for [`PackageManager::command`] that run a locally
+    /// installed binary, e.g. `exec tsc --noEmit` for pnpm.
+    pub fn exec_args(self, binary: &str, args: &[&str]) -> Vec<String> {
+        let prefix: &[&str] = match self {
+            PackageManager::Pnpm => &["exec"],
+            PackageManager::Npm => &["exec", "--no", "--"],
+            PackageManager::Yarn => &[],
+            PackageManager::Bun => &["x"],
+        };
+        prefix
+            .iter()
+            .chain([binary].iter())
+            .chain(args.iter())
+            .map(|arg| arg.to_string())
+            .collect()
+    }
+
+    /// Arguments for the package manager's vulnerability audit, failing at
+    /// `level` and above (any severity when `None`). Bun has no audit command
+    /// in the pinned release.
+    pub fn audit_args(self, level: Option<AuditLevel>) -> Option<Vec<String>> {
+        let mut args = vec!["audit".to_string()];
+        if let Some(level) = level {
+            let level = level.as_str().to_string();
+            match self {
+                PackageManager::Pnpm => args.extend(["--audit-level".to_string(), level]),
+                PackageManager::Npm => args.push(format!("--audit-level={}", level)),
+                PackageManager::Yarn => args.extend(["--level".to_string(), level]),
+                PackageManager::Bun => {}
+            }
+        }
+        match self {
+            PackageManager::Bun => None,
+            _ => Some(args),
+        }
+    }
+
+    /// Arguments for `pnpm dev --port <port>` and its equivalents; npm needs
+    /// `--` to forward the flag to the script.
+    pub fn dev_args(self, port: u16) -> Vec<String> {
+        let mut args = self.script_args("dev");
+        if self == PackageManager::Npm {
+            args.push("--".to_string());
+        }
+        args.push("--port".to_string());
+        args.push(port.to_string());
+        args
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum DeployTarget {
+    /// Vercel deploy button in the README
+    #[default]
+    Vercel,
+    /// Netlify deploy button and netlify.toml
+    Netlify,
+    /// Dockerfile for the standalone server, with build/run commands in the README
+    Docker,
+    /// No deploy section
+    None,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Desktop {
+    /// src-tauri/ shell around the static export
+    Tauri,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Styling {
+    /// Tailwind utility classes inline in the demo page and Button
+    #[default]
+    Tailwind,
+    /// page.module.css and Button.module.css; Tailwind stays available globally
+    CssModules,
+}
+
+/// Tool behind the `lint` and `lint:fix` scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Formatter {
+    /// `next lint` with .eslintrc.json
+    #[default]
+    EslintPrettier,
+    /// Biome lints and formats from biome.json; no ESLint config
+    Biome,
+}
+
+/// How src/styles is laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CssStructure {
+    /// Everything in globals.css
+    #[default]
+    Single,
+    /// globals.css importing utilities.css and components.css
+    Split,
+}
+
+/// How the generated components declare their props.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ComponentStyle {
+    /// `export const Button: React.FC<ButtonProps> = (...) => ...`
+    #[default]
+    Fc,
+    /// `export function Button({ ... }: ButtonProps)`, the style the React docs use
+    Fn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Logger {
+    /// No logger module
+    #[default]
+    None,
+    /// pino with pino-pretty output in development
+    Pino,
+    /// Thin structured wrapper around console
+    Console,
+}
+
+/// Lowest vulnerability severity that fails `--audit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AuditLevel {
+    Low,
+    Moderate,
+    High,
+    Critical,
+}
+
+impl AuditLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditLevel::Low => "low",
+            AuditLevel::Moderate => "moderate",
+            AuditLevel::High => "high",
+            AuditLevel::Critical => "critical",
+        }
+    }
+}
+
+/// Error reporting or tracing wired up in src/instrumentation.ts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Observability {
+    /// No instrumentation hook
+    #[default]
+    None,
+    /// @sentry/nextjs with client, server and edge configs
+    Sentry,
+    /// OpenTelemetry through @vercel/otel
+    Otel,
+}
+
+/// How `--seo` serves robots rules. Next.js rejects a project with both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Robots {
+    /// src/app/robots.ts, reading NEXT_PUBLIC_SITE_URL
+    #[default]
+    Dynamic,
+    /// public/robots.txt pointing at the development URL
+    Static,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ToolVersions {
+    /// No version manager file
+    #[default]
+    None,
+    /// .mise.toml
+    Mise,
+    /// .tool-versions for asdf
+    Asdf,
+}
+
+/// Bundle of options turned on by `--profile`. Applied on top of the
+/// command line, so `--no-*` flags still switch its options off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Profile {
+    /// Nothing beyond the defaults: no CI, git hooks or tests
+    Personal,
+    /// husky, Vitest and a CI workflow, linting with the default ESLint + Prettier
+    Team,
+    /// Team, plus security headers middleware, Dependabot, CODEOWNERS and a
+    /// strict install
+    Enterprise,
+}
+
+impl Profile {
+    pub fn apply(self, config: &mut ProjectConfig) {
+        if self == Profile::Personal {
+            return;
+        }
+        config.use_husky = true;
+        config.use_testing = true;
+        config.ci = true;
+
+        if self == Profile::Enterprise {
+            config.dependabot = true;
+            config.codeowners = true;
+            // A static --desktop export has no middleware, and a skipped install can't fail
+            config.middleware_chain |= config.desktop.is_none();
+            config.strict_install |= config.install_deps && !config.keep_going;
+        }
+    }
+}
+
+/// Optional UI primitive generated next to Button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Component {
+    /// Typed data table with column definitions
+    Table,
+    /// Padded container with optional border and shadow
+    Box,
+    /// Typography with heading, body and muted variants
+    Text,
+    /// Accessible loading spinner
+    Spinner,
+}
+
+impl Component {
+    /// Component and file name, e.g. `Spinner` for `Spinner.tsx`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Component::Table => "Table",
+            Component::Box => "Box",
+            Component::Text => "Text",
+            Component::Spinner => "Spinner",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReadmeStyle {
+    /// Full README with features, project structure and deploy notes
+    #[default]
+    Full,
+    /// Title and getting-started instructions only
+    Minimal,
+    /// Don't generate a README
+    None,
+}
+
+/// Major React release installed next to Next.js 15.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+pub enum ReactVersion {
+    /// Only supported by the Pages Router; kept for libraries not on 19 yet
+    #[value(name = "18")]
+    #[serde(rename = "18")]
+    V18,
+    /// What Next.js 15's App Router is built against
+    #[default]
+    #[value(name = "19")]
+    #[serde(rename = "19")]
+    V19,
+}
+
+/// Language of the README's headings and prose; commands stay the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReadmeLang {
+    /// English
+    #[default]
+    En,
+    /// Thai
+    Th,
+}
+
+/// Serde default for the options [`ProjectConfig::new`] turns on, so a config
+/// file that leaves them out matches the CLI's defaults.
+fn enabled() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct ProjectConfig {
     pub name: String,
     pub install_deps: bool,
     pub use_turbo: bool,
     pub use_react_query: bool,
+    pub init_git: bool,
+    pub granular_commits: bool,
+    pub demo_asset: bool,
+    pub use_husky: bool,
+    pub use_commitlint: bool,
+    /// Dev server port; `None` keeps Next.js' default of 3000.
+    pub port: Option<u16>,
+    /// Initial package.json version; `None` starts at 0.1.0.
+    pub package_version: Option<String>,
+    /// Publishable package.json exporting the components from dist/
+    pub publishable: bool,
+    pub open_dev_server: bool,
+    pub use_og_route: bool,
+    /// File upload route at /api/upload with a form at /upload
+    pub with_upload_example: bool,
+    /// Server action returning a typed `ActionResult`, with a form at /subscribe
+    pub server_actions: bool,
+    /// Backend the development-only catch-all route under /api forwards to
+    pub api_proxy: Option<String>,
+    /// src/middleware.ts chaining the handlers in src/middleware/
+    pub middleware_chain: bool,
+    /// Browser UI color for the layout's `viewport` export, e.g. `#2563eb`
+    pub theme_color: Option<String>,
+    pub readme: ReadmeStyle,
+    pub env_validation: EnvValidation,
+    /// Shared tsconfig the generated one extends, e.g. `../../tsconfig.base.json`
+    pub tsconfig_extends: Option<String>,
+    /// Keep the options a base config usually provides as local overrides
+    pub tsconfig_keep_options: bool,
+    /// `incremental` in tsconfig.json; off drops it and the `*.tsbuildinfo`
+    /// .gitignore entry
+    #[serde(default = "enabled")]
+    pub incremental: bool,
+    pub use_testing: bool,
+    pub styling: Styling,
+    pub react_version: ReactVersion,
+    pub css_structure: CssStructure,
+    pub with_skeletons: bool,
+    /// src/app/error.tsx retrying through Button, and with `with_skeletons`
+    /// a Skeleton component that loading.tsx is built from
+    pub app_states: bool,
+    /// src/app/not-found.tsx with a link back to the home page
+    pub not_found: bool,
+    /// useMediaQuery, useDebounce and useLocalStorage in src/hooks
+    pub common_hooks: bool,
+    /// postcss-nesting ahead of Tailwind in postcss.config.mjs
+    pub postcss_nesting: bool,
+    /// `import/no-unresolved` with the TypeScript resolver, so `@/` imports resolve
+    pub import_rules: bool,
+    pub formatter: Formatter,
+    pub package_manager: PackageManager,
+    /// Fail installs on peer dependency mismatches
+    pub strict_peers: bool,
+    #[serde(default = "enabled")]
+    pub auto_install_peers: bool,
+    /// Write package.json dependencies in alphabetical order
+    pub sort_deps: bool,
+    /// Wrap the config files `upgrade` manages in `aui:managed` markers
+    pub managed_markers: bool,
+    pub use_prisma: bool,
+    pub readme_features: ReadmeFeatures,
+    pub readme_lang: ReadmeLang,
+    /// Emoji in the README and the CLI's messages; off leaves plain text
+    #[serde(default = "enabled")]
+    pub emoji: bool,
+    /// Desktop shell generated around the app; implies a static export
+    pub desktop: Option<Desktop>,
+    /// URL added as `origin` after the initial commit
+    pub git_remote: Option<String>,
+    /// Branch the initial commit lands on; `None` uses [`DEFAULT_GIT_BRANCH`]
+    pub git_branch: Option<String>,
+    /// Generate components under src/components/ui with an index.ts barrel
+    pub components_barrel: bool,
+    /// UI primitives generated in addition to Button, without duplicates
+    pub components: Vec<Component>,
+    pub component_style: ComponentStyle,
+    /// A `<Name>.test.tsx` next to each extra component (needs `use_testing`)
+    pub scaffold_tests: bool,
+    pub deploy_target: DeployTarget,
+    /// Install without running lifecycle scripts (postinstall, prepare)
+    pub ignore_scripts: bool,
+    /// Install exactly what the lockfile records (`npm ci` and equivalents)
+    /// when the project has one
+    pub frozen_lockfile: bool,
+    /// Upstash rate limiting in src/libs/ratelimit.ts
+    pub use_ratelimit: bool,
+    /// src/app/sitemap.ts and robots.ts for the generated routes
+    pub use_seo: bool,
+    /// src/app/manifest.ts served as /manifest.webmanifest
+    pub manifest: bool,
+    /// robots.ts or robots.txt alongside the sitemap
+    pub robots: Robots,
+    /// Turn a missing git or a failed install into warnings instead of errors
+    pub keep_going: bool,
+    /// Print how long each generation phase took
+    pub trace: bool,
+    /// Generate into an existing directory as long as none of the files
+    /// written are there yet
+    pub additive: bool,
+    /// React Query `staleTime` in milliseconds; `None` keeps one minute
+    pub query_stale_time: Option<u64>,
+    /// React Query `retry` count; `None` keeps 1
+    pub query_retry: Option<u32>,
+    /// Default `apiRequest` timeout in milliseconds; `None` keeps 10 seconds
+    pub api_timeout: Option<u64>,
+    /// Mount `<ReactQueryDevtools />` in the query provider
+    #[serde(default = "enabled")]
+    pub query_devtools: bool,
+    /// Seconds before a hanging install is killed; `None` waits indefinitely
+    pub install_timeout: Option<u64>,
+    /// Run the package manager's vulnerability audit after installing
+    pub audit: bool,
+    /// Severity at which the audit fails generation; `None` only reports
+    pub audit_level: Option<AuditLevel>,
+    /// Shell command run in the project once it is generated and installed.
+    /// Only ever set from the command line: it is never written to or read
+    /// from a config file, so a shared config can't run commands.
+    #[serde(skip)]
+    pub postgen: Option<String>,
+    /// OpenAPI spec (path or URL) to generate src/types/api.d.ts from
+    pub openapi: Option<String>,
+    /// Directory whose contents are copied into public/
+    pub assets_from: Option<String>,
+    /// Scaffold directories from `DIRECTORIES` to leave out
+    pub skip_directories: Vec<String>,
+    /// Logger generated in src/libs/logger.ts
+    pub logger: Logger,
+    /// Instrumentation generated in src/instrumentation.ts
+    pub observability: Observability,
+    /// Version manager file pinning Node and pnpm
+    pub tool_versions: ToolVersions,
+    /// Show the name in Title Case in the page title and heading
+    pub title_case: bool,
+    /// Compile with Babel via .babelrc instead of SWC
+    pub use_babel: bool,
+    /// Self-host Inter in src/fonts with next/font/local
+    pub fonts_local: bool,
+    /// .vscode/tasks.json with the package manager's scripts, and CSS data
+    /// for Tailwind's at-rules
+    pub vscode: bool,
+    /// .devcontainer/devcontainer.json for Codespaces and remote containers
+    pub devcontainer: bool,
+    /// .github/dependabot.yml with weekly, grouped dependency updates
+    pub dependabot: bool,
+    /// .github/workflows/ci.yml running lint, tests and the build
+    pub ci: bool,
+    /// .github/CODEOWNERS, owned by the --git-remote account when there is one
+    pub codeowners: bool,
+    /// .watchmanconfig keeping watchman out of build output and dependencies
+    pub watchman: bool,
+    /// Fail the run when the install or post-install checks fail
+    pub strict_install: bool,
+    /// Run lint and type checks on the generated project after installing
+    pub verify_lint: bool,
+    /// Skip the output path safety checks
+    pub force: bool,
 }
 
 impl ProjectConfig {
+    /// Positional constructor kept for compatibility; prefer
+    /// [`ProjectConfig::builder`] as the number of options grows.
     pub fn new(name: String, install_deps: bool, use_turbo: bool, use_react_query: bool) -> Self {
         Self {
             name,
             install_deps,
             use_turbo,
             use_react_query,
+            auto_install_peers: true,
+            incremental: true,
+            query_devtools: true,
+            emoji: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn builder(name: impl Into<String>) -> ProjectConfigBuilder {
+        ProjectConfigBuilder::new(name)
+    }
+
+    /// The resolved config as TOML, as printed by `--print-config`. Unset
+    /// optional values are left out.
+    pub fn to_toml(&self) -> Result<String> {
+        Ok(toml::to_string(self)?)
+    }
+
+    /// Parses a config written by [`ProjectConfig::to_toml`]; missing keys
+    /// fall back to their defaults and unknown keys are rejected.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        Ok(toml::from_str(content)?)
+    }
+
+    /// The resolved config in `format`, for `--print-config --config-format`.
+    pub fn to_format(&self, format: ConfigFormat) -> Result<String> {
+        match format {
+            ConfigFormat::Toml => self.to_toml(),
+            ConfigFormat::Json => Ok(format!("{}\n", serde_json::to_string_pretty(self)?)),
+            ConfigFormat::Yaml => Ok(serde_yaml_ng::to_string(self)?),
+        }
+    }
+
+    /// Parses a config in `format`, with the same defaults and unknown-key
+    /// checks as [`ProjectConfig::from_toml`].
+    pub fn from_format(content: &str, format: ConfigFormat) -> Result<Self> {
+        match format {
+            ConfigFormat::Toml => Self::from_toml(content),
+            ConfigFormat::Json => Ok(serde_json::from_str(content)?),
+            ConfigFormat::Yaml => Ok(serde_yaml_ng::from_str(content)?),
+        }
+    }
+
+    /// Loads the config file at `path`, in the format its extension names.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let format = ConfigFormat::from_path(path)?;
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Could not read config '{}'", path.display()))?;
+        Self::from_format(&content, format).with_context(|| {
+            format!(
+                "'{}' is not a valid {} config",
+                path.display(),
+                format.name()
+            )
+        })
+    }
+
+    pub fn initial_branch(&self) -> &str {
+        self.git_branch.as_deref().unwrap_or(DEFAULT_GIT_BRANCH)
+    }
+
+    pub fn initial_version(&self) -> &str {
+        self.package_version
+            .as_deref()
+            .unwrap_or(DEFAULT_PACKAGE_VERSION)
+    }
+
+    pub fn dev_port(&self) -> u16 {
+        self.port.unwrap_or(DEFAULT_DEV_PORT)
+    }
+
+    pub fn dev_url(&self) -> String {
+        format!("http://localhost:{}", self.dev_port())
+    }
+
+    /// The scaffold directories to create: `DIRECTORIES` without the skipped
+    /// ones.
+    pub fn directories(&self) -> Vec<&'static str> {
+        DIRECTORIES
+            .iter()
+            .copied()
+            .filter(|dir| !self.skip_directories.iter().any(|skipped| skipped == dir))
+            .collect()
+    }
+
+    /// Whether to document environment variables in `.env.example`: when
+    /// validating them, or when a feature can't run without its secrets.
+    pub fn needs_env_example(&self) -> bool {
+        self.env_validation != EnvValidation::None
+            || self.use_prisma
+            || self.use_ratelimit
+            || self.observability == Observability::Sentry
+    }
+
+    /// Human-readable labels for the optional features this config enables.
+    pub fn optional_features(&self) -> Vec<&'static str> {
+        let mut features = Vec::new();
+        if self.use_turbo {
+            features.push("Turbopack for faster development");
+        }
+        if self.use_react_query {
+            features.push("React Query (TanStack Query)");
+        }
+        if self.styling == Styling::CssModules {
+            features.push("CSS Modules for the demo page and Button");
+        }
+        if self.css_structure == CssStructure::Split {
+            features.push("Global styles split into utilities.css and components.css");
+        }
+        if !self.components.is_empty() {
+            features.push("Extra UI components");
+        }
+        if self.components_barrel {
+            features.push("UI components exported from @/components/ui");
+        }
+        if self.with_skeletons {
+            features.push("Loading skeleton matching the home page");
+        }
+        if self.app_states {
+            features.push("Error page retrying with Button");
+        }
+        if self.not_found {
+            features.push("404 page linking back home");
+        }
+        if self.common_hooks {
+            features.push("Common hooks (media query, debounce, local storage)");
+        }
+        if self.postcss_nesting {
+            features.push("CSS nesting via postcss-nesting");
+        }
+        if self.import_rules {
+            features.push("ESLint import rules with the TypeScript resolver");
+        }
+        if self.formatter == Formatter::Biome {
+            features.push("Biome for linting and formatting");
+        }
+        if self.demo_asset {
+            features.push("Demo logo served from public/");
+        }
+        if self.use_prisma {
+            features.push("Prisma ORM");
+        }
+        if self.desktop == Some(Desktop::Tauri) {
+            features.push("Tauri desktop shell");
+        }
+        match self.deploy_target {
+            DeployTarget::Netlify => features.push("Netlify deployment config"),
+            DeployTarget::Docker => features.push("Dockerfile for standalone builds"),
+            DeployTarget::Vercel | DeployTarget::None => {}
+        }
+        if self.use_og_route {
+            features.push("Dynamic Open Graph images at /api/og");
+        }
+        if self.with_upload_example {
+            features.push("File upload example at /upload");
+        }
+        if self.server_actions {
+            features.push("Server action example at /subscribe with typed results");
+        }
+        if self.api_proxy.is_some() {
+            features.push("Development API proxy under /api");
+        }
+        if self.middleware_chain {
+            features.push("Composable middleware in src/middleware");
+        }
+        if self.publishable {
+            features.push("Publishable package exporting the components");
+        }
+        if self.use_ratelimit {
+            features.push("Upstash rate limiting");
+        }
+        if self.use_seo {
+            features.push("sitemap.xml and robots.txt");
+        }
+        if self.manifest {
+            features.push("Web app manifest");
+        }
+        if self.use_husky {
+            features.push("Husky git hooks");
+            if self.use_commitlint {
+                features.push("commitlint (Conventional Commits)");
+            }
+        }
+        if self.use_testing {
+            features.push("Vitest unit tests");
+        }
+        if self.assets_from.is_some() {
+            features.push("Your own assets copied into public/");
+        }
+        if self.openapi.is_some() {
+            features.push("API types generated from OpenAPI");
+        }
+        match self.logger {
+            Logger::Pino => features.push("pino logger"),
+            Logger::Console => features.push("Structured console logger"),
+            Logger::None => {}
+        }
+        match self.observability {
+            Observability::Sentry => features.push("Sentry error reporting"),
+            Observability::Otel => features.push("OpenTelemetry tracing (@vercel/otel)"),
+            Observability::None => {}
+        }
+        if self.use_babel {
+            features.push("Babel instead of SWC (.babelrc)");
+        }
+        if self.fonts_local {
+            features.push("Self-hosted Inter font (next/font/local)");
+        }
+        match self.tool_versions {
+            ToolVersions::Mise => features.push("Tool versions pinned in .mise.toml"),
+            ToolVersions::Asdf => features.push("Tool versions pinned in .tool-versions"),
+            ToolVersions::None => {}
+        }
+        if self.vscode {
+            features.push("VS Code tasks and Tailwind CSS data");
+        }
+        if self.devcontainer {
+            features.push("Dev container for Codespaces");
+        }
+        if self.dependabot {
+            features.push("Dependabot updates");
+        }
+        if self.ci {
+            features.push("GitHub Actions CI workflow");
+        }
+        if self.codeowners {
+            features.push("CODEOWNERS");
+        }
+        if self.watchman {
+            features.push("Watchman ignores for node_modules and .next");
+        }
+        match self.env_validation {
+            EnvValidation::None => {}
+            EnvValidation::Zod => features.push("Validated environment variables (zod)"),
+            EnvValidation::T3 => features.push("Validated environment variables (t3-env)"),
+        }
+        if self.init_git {
+            features.push("Git repository with initial commit");
+            if self.git_remote.is_some() {
+                features.push("origin remote configured");
+            }
+        }
+        features
+    }
+}
+
+/// Fluent builder for [`ProjectConfig`]. Unset options take the same values
+/// as accepting every default in the interactive prompts.
+#[derive(Debug, Clone)]
+pub struct ProjectConfigBuilder {
+    config: ProjectConfig,
+}
+
+impl ProjectConfigBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            config: ProjectConfig::new(name.into(), true, true, true),
+        }
+    }
+
+    pub fn install_deps(mut self, install_deps: bool) -> Self {
+        self.config.install_deps = install_deps;
+        self
+    }
+
+    pub fn turbo(mut self, use_turbo: bool) -> Self {
+        self.config.use_turbo = use_turbo;
+        self
+    }
+
+    pub fn react_query(mut self, use_react_query: bool) -> Self {
+        self.config.use_react_query = use_react_query;
+        self
+    }
+
+    pub fn git(mut self, init_git: bool) -> Self {
+        self.config.init_git = init_git;
+        self
+    }
+
+    pub fn granular_commits(mut self, granular_commits: bool) -> Self {
+        self.config.granular_commits = granular_commits;
+        self
+    }
+
+    pub fn demo_asset(mut self, demo_asset: bool) -> Self {
+        self.config.demo_asset = demo_asset;
+        self
+    }
+
+    pub fn husky(mut self, use_husky: bool) -> Self {
+        self.config.use_husky = use_husky;
+        self
+    }
+
+    pub fn commitlint(mut self, use_commitlint: bool) -> Self {
+        self.config.use_commitlint = use_commitlint;
+        self
+    }
+
+    pub fn port(mut self, port: u16) -> Self {
+        self.config.port = Some(port);
+        self
+    }
+
+    pub fn publishable(mut self, publishable: bool) -> Self {
+        self.config.publishable = publishable;
+        self
+    }
+
+    pub fn package_version(mut self, version: impl Into<String>) -> Self {
+        self.config.package_version = Some(version.into());
+        self
+    }
+
+    pub fn open_dev_server(mut self, open_dev_server: bool) -> Self {
+        self.config.open_dev_server = open_dev_server;
+        self
+    }
+
+    pub fn og_route(mut self, use_og_route: bool) -> Self {
+        self.config.use_og_route = use_og_route;
+        self
+    }
+
+    pub fn theme_color(mut self, theme_color: impl Into<String>) -> Self {
+        self.config.theme_color = Some(theme_color.into());
+        self
+    }
+
+    pub fn middleware_chain(mut self, middleware_chain: bool) -> Self {
+        self.config.middleware_chain = middleware_chain;
+        self
+    }
+
+    pub fn api_proxy(mut self, target: impl Into<String>) -> Self {
+        self.config.api_proxy = Some(target.into());
+        self
+    }
+
+    pub fn with_upload_example(mut self, with_upload_example: bool) -> Self {
+        self.config.with_upload_example = with_upload_example;
+        self
+    }
+
+    pub fn server_actions(mut self, server_actions: bool) -> Self {
+        self.config.server_actions = server_actions;
+        self
+    }
+
+    pub fn readme(mut self, readme: ReadmeStyle) -> Self {
+        self.config.readme = readme;
+        self
+    }
+
+    pub fn env_validation(mut self, env_validation: EnvValidation) -> Self {
+        self.config.env_validation = env_validation;
+        self
+    }
+
+    pub fn tsconfig_extends(mut self, base: impl Into<String>) -> Self {
+        self.config.tsconfig_extends = Some(base.into());
+        self
+    }
+
+    pub fn emoji(mut self, emoji: bool) -> Self {
+        self.config.emoji = emoji;
+        self
+    }
+
+    pub fn incremental(mut self, incremental: bool) -> Self {
+        self.config.incremental = incremental;
+        self
+    }
+
+    pub fn tsconfig_keep_options(mut self, keep: bool) -> Self {
+        self.config.tsconfig_keep_options = keep;
+        self
+    }
+
+    pub fn testing(mut self, use_testing: bool) -> Self {
+        self.config.use_testing = use_testing;
+        self
+    }
+
+    pub fn css_structure(mut self, css_structure: CssStructure) -> Self {
+        self.config.css_structure = css_structure;
+        self
+    }
+
+    pub fn react_version(mut self, react_version: ReactVersion) -> Self {
+        self.config.react_version = react_version;
+        self
+    }
+
+    pub fn styling(mut self, styling: Styling) -> Self {
+        self.config.styling = styling;
+        self
+    }
+
+    pub fn with_skeletons(mut self, with_skeletons: bool) -> Self {
+        self.config.with_skeletons = with_skeletons;
+        self
+    }
+
+    pub fn app_states(mut self, app_states: bool) -> Self {
+        self.config.app_states = app_states;
+        self
+    }
+
+    pub fn not_found(mut self, not_found: bool) -> Self {
+        self.config.not_found = not_found;
+        self
+    }
+
+    pub fn common_hooks(mut self, common_hooks: bool) -> Self {
+        self.config.common_hooks = common_hooks;
+        self
+    }
+
+    pub fn formatter(mut self, formatter: Formatter) -> Self {
+        self.config.formatter = formatter;
+        self
+    }
+
+    pub fn import_rules(mut self, import_rules: bool) -> Self {
+        self.config.import_rules = import_rules;
+        self
+    }
+
+    pub fn postcss_nesting(mut self, postcss_nesting: bool) -> Self {
+        self.config.postcss_nesting = postcss_nesting;
+        self
+    }
+
+    pub fn package_manager(mut self, package_manager: PackageManager) -> Self {
+        self.config.package_manager = package_manager;
+        self
+    }
+
+    pub fn strict_peers(mut self, strict_peers: bool) -> Self {
+        self.config.strict_peers = strict_peers;
+        self
+    }
+
+    pub fn audit(mut self, audit: bool) -> Self {
+        self.config.audit = audit;
+        self
+    }
+
+    pub fn audit_level(mut self, audit_level: AuditLevel) -> Self {
+        self.config.audit_level = Some(audit_level);
+        self
+    }
+
+    pub fn postgen(mut self, command: impl Into<String>) -> Self {
+        self.config.postgen = Some(command.into());
+        self
+    }
+
+    pub fn managed_markers(mut self, managed_markers: bool) -> Self {
+        self.config.managed_markers = managed_markers;
+        self
+    }
+
+    pub fn sort_deps(mut self, sort_deps: bool) -> Self {
+        self.config.sort_deps = sort_deps;
+        self
+    }
+
+    pub fn auto_install_peers(mut self, auto_install_peers: bool) -> Self {
+        self.config.auto_install_peers = auto_install_peers;
+        self
+    }
+
+    pub fn prisma(mut self, use_prisma: bool) -> Self {
+        self.config.use_prisma = use_prisma;
+        self
+    }
+
+    pub fn readme_features(mut self, readme_features: ReadmeFeatures) -> Self {
+        self.config.readme_features = readme_features;
+        self
+    }
+
+    pub fn readme_lang(mut self, readme_lang: ReadmeLang) -> Self {
+        self.config.readme_lang = readme_lang;
+        self
+    }
+
+    pub fn desktop(mut self, desktop: Desktop) -> Self {
+        self.config.desktop = Some(desktop);
+        self
+    }
+
+    pub fn git_remote(mut self, url: impl Into<String>) -> Self {
+        self.config.git_remote = Some(url.into());
+        self
+    }
+
+    pub fn git_branch(mut self, branch: impl Into<String>) -> Self {
+        self.config.git_branch = Some(branch.into());
+        self
+    }
+
+    pub fn scaffold_tests(mut self, scaffold_tests: bool) -> Self {
+        self.config.scaffold_tests = scaffold_tests;
+        self
+    }
+
+    pub fn component_style(mut self, component_style: ComponentStyle) -> Self {
+        self.config.component_style = component_style;
+        self
+    }
+
+    pub fn components_barrel(mut self, components_barrel: bool) -> Self {
+        self.config.components_barrel = components_barrel;
+        self
+    }
+
+    pub fn deploy_target(mut self, deploy_target: DeployTarget) -> Self {
+        self.config.deploy_target = deploy_target;
+        self
+    }
+
+    pub fn frozen_lockfile(mut self, frozen_lockfile: bool) -> Self {
+        self.config.frozen_lockfile = frozen_lockfile;
+        self
+    }
+
+    pub fn ignore_scripts(mut self, ignore_scripts: bool) -> Self {
+        self.config.ignore_scripts = ignore_scripts;
+        self
+    }
+
+    pub fn ratelimit(mut self, use_ratelimit: bool) -> Self {
+        self.config.use_ratelimit = use_ratelimit;
+        self
+    }
+
+    pub fn component(mut self, component: Component) -> Self {
+        if !self.config.components.contains(&component) {
+            self.config.components.push(component);
+            self.config.components.sort();
         }
+        self
+    }
+
+    pub fn seo(mut self, use_seo: bool) -> Self {
+        self.config.use_seo = use_seo;
+        self
+    }
+
+    pub fn manifest(mut self, manifest: bool) -> Self {
+        self.config.manifest = manifest;
+        self
+    }
+
+    pub fn query_stale_time(mut self, ms: u64) -> Self {
+        self.config.query_stale_time = Some(ms);
+        self
+    }
+
+    pub fn query_retry(mut self, retries: u32) -> Self {
+        self.config.query_retry = Some(retries);
+        self
+    }
+
+    pub fn api_timeout(mut self, ms: u64) -> Self {
+        self.config.api_timeout = Some(ms);
+        self
+    }
+
+    pub fn query_devtools(mut self, query_devtools: bool) -> Self {
+        self.config.query_devtools = query_devtools;
+        self
+    }
+
+    pub fn skip_directory(mut self, dir: impl Into<String>) -> Self {
+        self.config.skip_directories.push(dir.into());
+        self
+    }
+
+    pub fn assets_from(mut self, dir: impl Into<String>) -> Self {
+        self.config.assets_from = Some(dir.into());
+        self
+    }
+
+    pub fn openapi(mut self, spec: impl Into<String>) -> Self {
+        self.config.openapi = Some(spec.into());
+        self
+    }
+
+    pub fn install_timeout(mut self, seconds: u64) -> Self {
+        self.config.install_timeout = Some(seconds);
+        self
+    }
+
+    pub fn robots(mut self, robots: Robots) -> Self {
+        self.config.robots = robots;
+        self
+    }
+
+    pub fn logger(mut self, logger: Logger) -> Self {
+        self.config.logger = logger;
+        self
+    }
+
+    pub fn observability(mut self, observability: Observability) -> Self {
+        self.config.observability = observability;
+        self
+    }
+
+    pub fn tool_versions(mut self, tool_versions: ToolVersions) -> Self {
+        self.config.tool_versions = tool_versions;
+        self
+    }
+
+    pub fn title_case(mut self, title_case: bool) -> Self {
+        self.config.title_case = title_case;
+        self
+    }
+
+    pub fn babel(mut self, use_babel: bool) -> Self {
+        self.config.use_babel = use_babel;
+        self
+    }
+
+    pub fn fonts_local(mut self, fonts_local: bool) -> Self {
+        self.config.fonts_local = fonts_local;
+        self
+    }
+
+    pub fn devcontainer(mut self, devcontainer: bool) -> Self {
+        self.config.devcontainer = devcontainer;
+        self
+    }
+
+    pub fn vscode(mut self, vscode: bool) -> Self {
+        self.config.vscode = vscode;
+        self
+    }
+
+    pub fn dependabot(mut self, dependabot: bool) -> Self {
+        self.config.dependabot = dependabot;
+        self
+    }
+
+    pub fn ci(mut self, ci: bool) -> Self {
+        self.config.ci = ci;
+        self
+    }
+
+    pub fn codeowners(mut self, codeowners: bool) -> Self {
+        self.config.codeowners = codeowners;
+        self
+    }
+
+    pub fn watchman(mut self, watchman: bool) -> Self {
+        self.config.watchman = watchman;
+        self
+    }
+
+    pub fn strict_install(mut self, strict_install: bool) -> Self {
+        self.config.strict_install = strict_install;
+        self
+    }
+
+    pub fn verify_lint(mut self, verify_lint: bool) -> Self {
+        self.config.verify_lint = verify_lint;
+        self
+    }
+
+    pub fn force(mut self, force: bool) -> Self {
+        self.config.force = force;
+        self
+    }
+
+    pub fn additive(mut self, additive: bool) -> Self {
+        self.config.additive = additive;
+        self
+    }
+
+    pub fn trace(mut self, trace: bool) -> Self {
+        self.config.trace = trace;
+        self
+    }
+
+    pub fn keep_going(mut self, keep_going: bool) -> Self {
+        self.config.keep_going = keep_going;
+        self
+    }
+
+    pub fn build(self) -> ProjectConfig {
+        self.config
     }
 }
 
@@ -39,9 +1309,9 @@ mod tests {
         let config = ProjectConfig::new("test-project".to_string(), true, false, true);
 
         assert_eq!(config.name, "test-project");
-        assert_eq!(config.install_deps, true);
-        assert_eq!(config.use_turbo, false);
-        assert_eq!(config.use_react_query, true);
+        assert!(config.install_deps);
+        assert!(!config.use_turbo);
+        assert!(config.use_react_query);
     }
 
     #[test]
@@ -52,6 +1322,121 @@ mod tests {
         assert_eq!(config1, config2);
     }
 
+    #[test]
+    fn test_builder_matches_positional_constructor() {
+        let built = ProjectConfig::builder("built")
+            .install_deps(false)
+            .turbo(false)
+            .react_query(true)
+            .build();
+        let positional = ProjectConfig::new("built".to_string(), false, false, true);
+
+        assert_eq!(built, positional);
+    }
+
+    #[test]
+    fn test_builder_defaults_match_interactive_defaults() {
+        let config = ProjectConfig::builder("defaults").build();
+
+        // Accepting every prompt: install deps, Turbopack and React Query
+        assert_eq!(
+            config,
+            ProjectConfig::new("defaults".to_string(), true, true, true)
+        );
+        assert!(!config.init_git);
+        assert!(!config.use_husky);
+    }
+
+    #[test]
+    fn test_toml_round_trips() {
+        let config = ProjectConfig::builder("toml-app")
+            .port(4000)
+            .env_validation(EnvValidation::T3)
+            .package_manager(PackageManager::Bun)
+            .styling(Styling::CssModules)
+            .deploy_target(DeployTarget::None)
+            .desktop(Desktop::Tauri)
+            .tsconfig_extends("../../tsconfig.base.json")
+            .husky(true)
+            .build();
+
+        let toml = config.to_toml().unwrap();
+
+        assert!(toml.contains("package_manager = \"bun\""));
+        assert!(toml.contains("styling = \"css-modules\""));
+        assert!(!toml.contains("git_remote"));
+        assert_eq!(ProjectConfig::from_toml(&toml).unwrap(), config);
+    }
+
+    #[test]
+    fn test_partial_config_defaults_match_new() {
+        let expected = ProjectConfig::new("partial-app".to_string(), false, false, false);
+        let config = ProjectConfig::from_toml("name = \"partial-app\"\n").unwrap();
+        assert_eq!(config, expected);
+
+        let config = ProjectConfig::from_toml("name = \"partial-app\"\nemoji = false\n").unwrap();
+        assert!(!config.emoji);
+        assert!(config.incremental && config.query_devtools && config.auto_install_peers);
+    }
+
+    #[test]
+    fn test_config_formats_load_the_same_config() {
+        let toml = "name = \"fmt-app\"\npackage_manager = \"bun\"\nuse_seo = true\ncomponents = [\"spinner\"]\n";
+        let json = r#"{ "name": "fmt-app", "package_manager": "bun", "use_seo": true, "components": ["spinner"] }"#;
+        let yaml = "name: fmt-app\npackage_manager: bun\nuse_seo: true\ncomponents:\n  - spinner\n";
+
+        let from_toml = ProjectConfig::from_format(toml, ConfigFormat::Toml).unwrap();
+        assert_eq!(from_toml.package_manager, PackageManager::Bun);
+        assert_eq!(
+            ProjectConfig::from_format(json, ConfigFormat::Json).unwrap(),
+            from_toml
+        );
+        assert_eq!(
+            ProjectConfig::from_format(yaml, ConfigFormat::Yaml).unwrap(),
+            from_toml
+        );
+
+        for format in [ConfigFormat::Json, ConfigFormat::Yaml] {
+            let printed = from_toml.to_format(format).unwrap();
+            assert_eq!(
+                ProjectConfig::from_format(&printed, format).unwrap(),
+                from_toml
+            );
+        }
+        assert!(ProjectConfig::from_format("name: x\ncolour: true\n", ConfigFormat::Yaml).is_err());
+
+        assert_eq!(
+            ConfigFormat::from_path(Path::new("team.YML")).unwrap(),
+            ConfigFormat::Yaml
+        );
+        assert!(ConfigFormat::from_path(Path::new("team.ini")).is_err());
+        assert!(ConfigFormat::from_path(Path::new("team")).is_err());
+    }
+
+    #[test]
+    fn test_builder_sets_optional_features() {
+        let config = ProjectConfig::builder("hooks")
+            .git(true)
+            .granular_commits(true)
+            .husky(true)
+            .commitlint(true)
+            .demo_asset(true)
+            .build();
+
+        assert!(config.init_git && config.granular_commits);
+        assert!(config.use_husky && config.use_commitlint);
+        assert!(config.demo_asset);
+    }
+
+    #[test]
+    fn test_dev_url_uses_default_and_custom_port() {
+        let default = ProjectConfig::builder("app").build();
+        assert_eq!(default.dev_url(), "http://localhost:3000");
+
+        let custom = ProjectConfig::builder("app").port(4000).build();
+        assert_eq!(custom.dev_url(), "http://localhost:4000");
+    }
+
     #[test]
     fn test_directories_contains_expected_paths() {
         assert!(DIRECTORIES.contains(&"src/app"));
@@ -0,0 +1,109 @@
+//! Plain-text output for `--no-emoji`, for wikis and terminals that render
+//! emoji poorly. Status emoji become ASCII markers; the rest are dropped.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static EMOJI: AtomicBool = AtomicBool::new(true);
+
+/// Turns emoji in the CLI's printed messages on or off for the whole run.
+pub fn set_emoji(enabled: bool) {
+    EMOJI.store(enabled, Ordering::Relaxed);
+}
+
+/// `text` as the terminal should show it: unchanged, or without emoji under
+/// `--no-emoji`.
+pub fn terminal_text(text: &str) -> String {
+    if EMOJI.load(Ordering::Relaxed) {
+        text.to_string()
+    } else {
+        strip_emoji(text)
+    }
+}
+
+/// `println!` for messages that start with an emoji.
+macro_rules! say {
+    ($($arg:tt)*) => {
+        println!("{}", $crate::emoji::terminal_text(&format!($($arg)*)))
+    };
+}
+pub(crate) use say;
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF
+            | 0x2600..=0x27BF
+            | 0x2B00..=0x2BFF
+            | 0x231A..=0x23FF
+            | 0xFE0F
+            | 0x200D
+    )
+}
+
+fn marker(c: char) -> Option<&'static str> {
+    match c {
+        '✅' => Some("[ok]"),
+        '❌' => Some("[error]"),
+        '⚠' => Some("[warn]"),
+        _ => None,
+    }
+}
+
+/// `text` with status emoji replaced by `[ok]`, `[error]` and `[warn]`, and
+/// every other emoji removed along with the spacing that followed it.
+pub fn strip_emoji(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if !is_emoji(c) {
+            plain.push(c);
+            continue;
+        }
+
+        // Variation selectors and joiners belong to the emoji before them
+        while chars
+            .peek()
+            .is_some_and(|&next| matches!(next as u32, 0xFE0F | 0x200D))
+        {
+            chars.next();
+        }
+        let mut spaced = false;
+        while chars.peek() == Some(&' ') {
+            chars.next();
+            spaced = true;
+        }
+
+        if let Some(marker) = marker(c) {
+            plain.push_str(marker);
+            if spaced {
+                plain.push(' ');
+            }
+        }
+    }
+    plain
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_emoji_keeps_status_markers() {
+        assert_eq!(
+            strip_emoji("✅ Git repository initialized"),
+            "[ok] Git repository initialized"
+        );
+        assert_eq!(strip_emoji("⚠️  1 warning"), "[warn] 1 warning");
+        assert_eq!(
+            strip_emoji("\n🏗️  Creating project: app"),
+            "\nCreating project: app"
+        );
+        assert_eq!(strip_emoji("⏱️  Done in 0.4s"), "Done in 0.4s");
+        assert_eq!(
+            strip_emoji("- ✨ Turbopack\n- ⚡ Next.js 15"),
+            "- Turbopack\n- Next.js 15"
+        );
+        assert_eq!(strip_emoji("├── src/ • café"), "├── src/ • café");
+    }
+}
@@ -1,68 +1,1676 @@
 use anyhow::Result;
 use colored::Colorize;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+use serde::Serialize;
 use std::fs;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::config::{ProjectConfig, DIRECTORIES};
+use crate::config::{
+    DeployTarget, Formatter, Logger, Observability, ProjectConfig, Styling, ToolVersions,
+};
+use crate::emoji::{say, terminal_text};
 use crate::files::*;
-use crate::validation::install_dependencies_with_pnpm;
+use crate::git::{
+    add_remote, commit_all, feature_commit_message, init_repository, is_git_available,
+};
+use crate::openapi::openapi_types_files;
+use crate::runner::{CommandRunner, SystemRunner};
+use crate::validation::{
+    check_output_path, check_path_limits, frozen_lockfile_warning, install_dependencies,
+    option_warnings, Warning,
+};
 
-pub fn generate_project(config: &ProjectConfig) -> Result<()> {
-    let project_path = Path::new(&config.name);
+/// A single file-generation step, tagged with the feature it belongs to.
+pub struct GenerationStep {
+    pub feature: &'static str,
+    pub run: fn(&ProjectConfig) -> Result<Files>,
+}
+
+impl GenerationStep {
+    fn new(feature: &'static str, run: fn(&ProjectConfig) -> Result<Files>) -> Self {
+        Self { feature, run }
+    }
+}
+
+/// Ordered list of file-generation steps for `config`. Steps of the same
+/// feature are contiguous: base files first, then styling, then each
+/// optional feature.
+pub fn generation_steps(config: &ProjectConfig) -> Vec<GenerationStep> {
+    let mut steps = vec![
+        GenerationStep::new("base", package_json_files),
+        GenerationStep::new("base", tsconfig_files),
+        GenerationStep::new("base", next_config_files),
+        match config.formatter {
+            Formatter::EslintPrettier => GenerationStep::new("base", eslint_config_files),
+            Formatter::Biome => GenerationStep::new("base", |_| biome_config_files()),
+        },
+        GenerationStep::new("base", gitignore_files),
+        GenerationStep::new("base", npmrc_files),
+        GenerationStep::new("base", |c| app_layout_files(&display_name(c), c)),
+        GenerationStep::new("base", |c| app_page_files(&display_name(c), c)),
+        GenerationStep::new("base", |c| readme_files(&c.name, c)),
+    ];
+
+    if !env_vars(config).is_empty() {
+        steps.push(GenerationStep::new("base", env_types_files));
+    }
+
+    steps.extend([
+        GenerationStep::new("styling", postcss_config_files),
+        GenerationStep::new("styling", globals_css_files),
+        GenerationStep::new("styling", button_component_files),
+    ]);
+
+    if config.styling == Styling::CssModules {
+        steps.push(GenerationStep::new("styling", |_| page_module_css_files()));
+        steps.push(GenerationStep::new("styling", button_module_css_files));
+    }
+
+    if config.components_barrel {
+        steps.push(GenerationStep::new("styling", components_barrel_files));
+    }
+
+    if !config.components.is_empty() {
+        steps.push(GenerationStep::new("components", components_files));
+    }
+
+    if config.with_skeletons {
+        steps.push(GenerationStep::new("skeletons", loading_skeleton_files));
+    }
+
+    if config.common_hooks {
+        steps.push(GenerationStep::new("hooks", |_| common_hooks_files()));
+    }
+
+    if config.app_states {
+        steps.push(GenerationStep::new("app-states", error_page_files));
+    }
+
+    if config.not_found {
+        steps.push(GenerationStep::new("not-found", |c| {
+            not_found_files(&display_name(c))
+        }));
+    }
+
+    if config.use_babel {
+        steps.push(GenerationStep::new("babel", |_| babelrc_files()));
+    }
+
+    if config.demo_asset {
+        steps.push(GenerationStep::new("demo-asset", |c| {
+            logo_svg_files(&c.name)
+        }));
+    }
+
+    if config.needs_env_example() {
+        steps.push(GenerationStep::new("env", env_files));
+    }
+
+    if config.use_prisma {
+        steps.push(GenerationStep::new("prisma", |_| prisma_schema_files()));
+        steps.push(GenerationStep::new("prisma", |_| prisma_client_files()));
+    }
+
+    if config.desktop.is_some() {
+        steps.push(GenerationStep::new("desktop", tauri_shell_files));
+    }
+
+    match config.deploy_target {
+        DeployTarget::Netlify => steps.push(GenerationStep::new("deploy", netlify_config_files)),
+        DeployTarget::Docker => steps.push(GenerationStep::new("deploy", dockerfile_files)),
+        DeployTarget::Vercel | DeployTarget::None => {}
+    }
+
+    if config.logger != Logger::None {
+        steps.push(GenerationStep::new("logger", logger_files));
+    }
+
+    if config.observability != Observability::None {
+        steps.push(GenerationStep::new("observability", instrumentation_files));
+    }
+
+    if config.use_ratelimit {
+        steps.push(GenerationStep::new("ratelimit", |_| ratelimit_files()));
+    }
+
+    if config.use_seo {
+        steps.push(GenerationStep::new("seo", seo_routes_files));
+    }
+
+    if config.manifest {
+        steps.push(GenerationStep::new("manifest", manifest_files));
+    }
+
+    if config.use_og_route {
+        steps.push(GenerationStep::new("og-route", |c| {
+            og_route_files(&c.name, c)
+        }));
+    }
+
+    if config.with_upload_example {
+        steps.push(GenerationStep::new("upload", upload_route_files));
+    }
+
+    if config.server_actions {
+        steps.push(GenerationStep::new("server-actions", server_actions_files));
+    }
+
+    if config.api_proxy.is_some() {
+        steps.push(GenerationStep::new("api-proxy", proxy_route_files));
+    }
+
+    if config.middleware_chain {
+        steps.push(GenerationStep::new("middleware", middleware_chain_files));
+    }
+
+    if config.use_husky {
+        steps.push(GenerationStep::new("husky", husky_hooks_files));
+
+        if config.use_commitlint {
+            steps.push(GenerationStep::new("commitlint", commitlint_config_files));
+        }
+    }
+
+    if config.openapi.is_some() {
+        steps.push(GenerationStep::new("openapi", openapi_types_files));
+    }
+
+    if config.use_react_query {
+        steps.push(GenerationStep::new("react-query", query_provider_files));
+        steps.push(GenerationStep::new("react-query", api_client_files));
+        steps.push(GenerationStep::new("react-query", |_| {
+            example_hooks_files()
+        }));
+    }
+
+    if config.use_testing {
+        steps.push(GenerationStep::new("testing", |_| vitest_config_files()));
+        steps.push(GenerationStep::new("testing", button_test_files));
+        steps.push(GenerationStep::new("testing", alias_test_files));
+
+        if config.scaffold_tests && !config.components.is_empty() {
+            steps.push(GenerationStep::new("testing", component_tests_files));
+        }
+
+        if config.use_react_query {
+            steps.push(GenerationStep::new("testing", |_| api_test_files()));
+        }
+    }
+
+    if config.tool_versions != ToolVersions::None {
+        steps.push(GenerationStep::new("tool-versions", tool_versions_files));
+    }
+
+    if config.vscode {
+        steps.push(GenerationStep::new("vscode", vscode_tasks_files));
+        steps.push(GenerationStep::new("vscode", |_| css_custom_data_files()));
+    }
+
+    if config.devcontainer {
+        steps.push(GenerationStep::new("devcontainer", devcontainer_files));
+    }
+
+    if config.dependabot {
+        steps.push(GenerationStep::new("dependabot", dependabot_config_files));
+    }
+
+    if config.ci {
+        steps.push(GenerationStep::new("ci", ci_workflow_files));
+    }
+
+    if config.codeowners {
+        steps.push(GenerationStep::new("codeowners", codeowners_files));
+    }
+
+    if config.watchman {
+        steps.push(GenerationStep::new("watchman", watchman_config_files));
+    }
+
+    if config.publishable {
+        steps.push(GenerationStep::new("publishable", library_entry_files));
+    }
+
+    // Last, so files copied from --assets-from never replace generated ones
+    if config.assets_from.is_some() {
+        steps.push(GenerationStep::new("assets", assets_files));
+    }
+
+    steps
+}
+
+/// Outcome of a successful generation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationReport {
+    /// The `[n/total]` progress lines, in the order they were printed
+    pub progress: Vec<String>,
+    /// Non-fatal problems, for the summary printed at the end
+    pub warnings: Vec<Warning>,
+    pub elapsed: Duration,
+    /// Time spent in each phase that ran, in the order they first ran
+    pub phases: Vec<PhaseTiming>,
+}
+
+/// Wall-clock time spent in one generation phase, for `--trace`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhaseTiming {
+    pub phase: &'static str,
+    pub elapsed: Duration,
+}
+
+/// Numbers the major generation steps as `[n/total]`, and times the phases
+/// they belong to.
+struct Progress {
+    total: usize,
+    lines: Vec<String>,
+    phases: Vec<PhaseTiming>,
+    lap_started: Instant,
+    bar: Option<ProgressBar>,
+}
 
-    if project_path.exists() {
+impl Progress {
+    fn new(total: usize) -> Self {
+        Self {
+            total,
+            lines: Vec::new(),
+            phases: Vec::new(),
+            lap_started: Instant::now(),
+            bar: None,
+        }
+    }
+
+    /// Also shows each step on `bar`, for a project generated alongside others.
+    fn with_bar(mut self, bar: Option<ProgressBar>) -> Self {
+        if let Some(bar) = &bar {
+            bar.set_length(self.total as u64);
+        }
+        self.bar = bar;
+        self
+    }
+
+    /// Adds the time since the last lap to `phase`.
+    fn lap(&mut self, phase: &'static str) {
+        self.record(phase, self.lap_started.elapsed());
+        self.lap_started = Instant::now();
+    }
+
+    fn record(&mut self, phase: &'static str, elapsed: Duration) {
+        match self.phases.iter_mut().find(|timing| timing.phase == phase) {
+            Some(timing) => timing.elapsed += elapsed,
+            None => self.phases.push(PhaseTiming { phase, elapsed }),
+        }
+    }
+
+    fn advance(&mut self, label: &str) {
+        let line = format!(
+            "[{}/{}] {}",
+            self.lines.len() + 1,
+            self.total,
+            terminal_text(label)
+        );
+        announce(&line.blue().to_string());
+        if let Some(bar) = &self.bar {
+            bar.set_position(self.lines.len() as u64);
+            bar.set_message(terminal_text(label));
+        }
+        self.lines.push(line);
+    }
+}
+
+/// Number of progress steps generating `config` takes: the directory
+/// structure, each file step, then git, the install and its checks when
+/// requested.
+pub fn plan_length(config: &ProjectConfig) -> usize {
+    1 + generation_steps(config).len()
+        + usize::from(config.init_git)
+        + usize::from(config.install_deps)
+        + usize::from(config.install_deps && config.verify_lint)
+        + usize::from(config.install_deps && config.audit)
+        + usize::from(config.postgen.is_some())
+}
+
+pub fn generate_project(config: &ProjectConfig) -> Result<GenerationReport> {
+    generate_project_in(Path::new("."), config)
+}
+
+/// Generates the project as `base_dir/<name>`.
+pub fn generate_project_in(base_dir: &Path, config: &ProjectConfig) -> Result<GenerationReport> {
+    generate_with_warnings(base_dir, config, project_warnings(base_dir, config), None)
+}
+
+/// The warnings about `config`'s options, for generating it under `base_dir`.
+fn project_warnings(base_dir: &Path, config: &ProjectConfig) -> Vec<Warning> {
+    let mut warnings = option_warnings(config);
+    warnings.extend(frozen_lockfile_warning(
+        config,
+        &base_dir.join(&config.name),
+    ));
+    warnings
+}
+
+/// [`generate_project_in`], starting from `warnings` rather than the ones
+/// `config` itself raises, and reporting each step on `bar` when given one.
+fn generate_with_warnings(
+    base_dir: &Path,
+    config: &ProjectConfig,
+    mut warnings: Vec<Warning>,
+    bar: Option<ProgressBar>,
+) -> Result<GenerationReport> {
+    let started = Instant::now();
+    let project_path = base_dir.join(&config.name);
+    check_output_path(&project_path, config.force)?;
+    check_path_limits(&project_path)?;
+
+    let existing = project_path.is_dir() && config.additive;
+    if project_path.exists() && !existing {
         anyhow::bail!("Directory '{}' already exists!", config.name);
     }
 
-    println!("\n🏗️  Creating project: {}", config.name.yellow());
+    // Download before anything is written; offline, fall back to Google-hosted Inter
+    let mut font = None;
+    let mut fallback = None;
+    if config.fonts_local && !config.use_babel {
+        match fetch_local_font(&SystemRunner) {
+            Ok(bytes) => font = Some(bytes),
+            Err(err) => {
+                warnings.push(Warning::new(format!(
+                    "Could not download Inter for --fonts-local ({}); the layout uses next/font/google instead",
+                    err
+                )));
+                fallback = Some(ProjectConfig {
+                    fonts_local: false,
+                    ..config.clone()
+                });
+            }
+        }
+    }
+    let config = fallback.as_ref().unwrap_or(config);
+
+    let use_git = config.init_git && is_git_available();
+    if config.init_git && !use_git {
+        if !config.keep_going {
+            anyhow::bail!("git is not installed or not in PATH");
+        }
+        warnings.push(Warning::new(
+            "git is not installed or not in PATH; skipped the repository",
+        ));
+    }
+
+    if existing {
+        check_additive(&project_path, config, font.is_some(), use_git)?;
+    }
 
-    fs::create_dir(project_path)?;
-    create_directories(project_path)?;
-    create_files(project_path, config)?;
+    announce(&terminal_text(&format!(
+        "\n🏗️  Creating project: {}",
+        config.name.yellow()
+    )));
+    let mut progress = Progress::new(plan_length(config)).with_bar(bar);
+    progress.record("checks", started.elapsed());
 
+    if !existing {
+        fs::create_dir(&project_path)?;
+    }
+    progress.advance("📁 Creating directory structure...");
+    create_directories(&project_path, config)?;
+    if let Some(bytes) = &font {
+        fs::create_dir_all(project_path.join("src/fonts"))?;
+        write_bytes(&project_path, LOCAL_FONT_PATH, bytes)?;
+    }
+    progress.lap("directories");
+
+    if config.granular_commits && use_git {
+        progress.advance("🌱 Initializing git repository...");
+        init_repository(&project_path, config.initial_branch())?;
+        progress.lap("git");
+        // Includes the commit after each feature
+        create_files_with_commits(&project_path, config, &mut progress)?;
+        progress.lap("files");
+    } else {
+        create_files(&project_path, config, &mut progress)?;
+        progress.lap("files");
+
+        if use_git {
+            progress.advance("🌱 Initializing git repository...");
+            init_repository(&project_path, config.initial_branch())?;
+            commit_all(&project_path, "Initial commit from AUI Next.js Generator")?;
+            progress.lap("git");
+        } else if config.init_git {
+            progress.advance("🌱 Skipping git repository...");
+        }
+    }
+
+    if let (true, Some(url)) = (use_git, &config.git_remote) {
+        add_remote(&project_path, "origin", url)?;
+        progress.lap("git");
+    }
+
+    finish_project(&project_path, config, &mut progress, &mut warnings)?;
+
+    let elapsed = started.elapsed();
+    announce(&terminal_text(&format!(
+        "⏱️  Done in {:.1}s",
+        elapsed.as_secs_f64()
+    )));
+
+    Ok(GenerationReport {
+        progress: progress.lines,
+        warnings,
+        elapsed,
+        phases: progress.phases,
+    })
+}
+
+/// The steps after the files are written: installing dependencies, auditing
+/// and verifying them, then the `--postgen` command.
+fn finish_project(
+    project_path: &Path,
+    config: &ProjectConfig,
+    progress: &mut Progress,
+    warnings: &mut Vec<Warning>,
+) -> Result<()> {
     // Install dependencies as the final step
     if config.install_deps {
-        println!("\n📦 Installing dependencies...");
-        install_dependencies_with_pnpm(project_path)?;
+        progress.advance("📦 Installing dependencies...");
+        let installed = match install_dependencies(&SystemRunner, project_path, config) {
+            Ok(()) => true,
+            Err(err) if config.keep_going => {
+                warnings.push(Warning::new(format!(
+                    "Installing dependencies failed ({}); run `{} install` in the project",
+                    err,
+                    config.package_manager.command()
+                )));
+                false
+            }
+            Err(err) => return Err(err),
+        };
+        progress.lap("install");
+
+        if config.audit && installed {
+            progress.advance("🛡️  Auditing dependencies...");
+            warnings.extend(audit_dependencies(&SystemRunner, project_path, config)?);
+            progress.lap("audit");
+        } else if config.audit {
+            progress.advance("🛡️  Skipping dependency audit...");
+        }
+
+        if config.verify_lint {
+            progress.advance("🔍 Checking lint and types...");
+            warnings.extend(verify_project(&SystemRunner, project_path, config)?);
+            progress.lap("verify");
+        }
+    }
+
+    if let Some(command) = &config.postgen {
+        progress.advance("⚙️  Running the --postgen command...");
+        match run_postgen(&SystemRunner, project_path, command) {
+            Ok(()) => {}
+            Err(err) if config.keep_going => warnings.push(Warning::new(err.to_string())),
+            Err(err) => return Err(err),
+        }
+        progress.lap("postgen");
     }
 
     Ok(())
 }
 
-fn create_directories(project_path: &Path) -> Result<()> {
-    println!("{}", "📁 Creating directory structure...".blue());
+/// Projects scaffolded at the same time when several are generated.
+pub const DEFAULT_JOBS: usize = 4;
+
+/// Outcome of one of several projects generated together.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectOutcome {
+    pub name: String,
+    pub result: std::result::Result<GenerationReport, String>,
+}
+
+/// Outcome of generating several projects, in the order they were given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultiProjectReport {
+    pub projects: Vec<ProjectOutcome>,
+}
+
+impl MultiProjectReport {
+    pub fn failures(&self) -> impl Iterator<Item = &ProjectOutcome> {
+        self.projects
+            .iter()
+            .filter(|project| project.result.is_err())
+    }
+}
+
+/// `config` without the steps that run after the files are written.
+fn scaffold_config(config: &ProjectConfig) -> ProjectConfig {
+    ProjectConfig {
+        install_deps: false,
+        postgen: None,
+        ..config.clone()
+    }
+}
+
+/// A status bar for the project `name`, with names padded to `width` so the
+/// bars line up.
+fn project_bar(name: &str, width: usize) -> ProgressBar {
+    let style =
+        ProgressStyle::with_template("{prefix:.bold} [{bar:24.cyan/blue}] {pos}/{len} {wide_msg}")
+            .expect("the template is valid")
+            .progress_chars("=> ");
+    let bar = ProgressBar::new(0).with_style(style);
+    bar.set_prefix(format!("{:width$}", name, width = width));
+    bar.set_message(terminal_text("⏳ Waiting..."));
+    bar
+}
+
+/// Ends `bar` on the outcome of its project.
+fn finish_bar(bar: &ProgressBar, result: &std::result::Result<GenerationReport, String>) {
+    match result {
+        Ok(_) => {
+            bar.set_position(bar.length().unwrap_or_default());
+            bar.finish_with_message(terminal_text("✅ Done"));
+        }
+        Err(err) => {
+            bar.abandon_with_message(terminal_text(&format!("❌ {}", err).red().to_string()))
+        }
+    }
+}
+
+/// Generates each of `configs` under `base_dir`, with a status bar per
+/// project. Up to `jobs` projects are scaffolded at once; installs then run
+/// one project at a time so they don't contend for the registry and the
+/// package store. A failing project is reported and the others carry on.
+pub fn generate_projects(
+    base_dir: &Path,
+    configs: &[ProjectConfig],
+    jobs: usize,
+) -> MultiProjectReport {
+    let multi = MultiProgress::new();
+    let width = configs
+        .iter()
+        .map(|config| config.name.len())
+        .max()
+        .unwrap_or(0);
+    let bars: Vec<ProgressBar> = configs
+        .iter()
+        .map(|config| multi.add(project_bar(&config.name, width)))
+        .collect();
+
+    let scaffolded = scaffold_projects(base_dir, configs, jobs, &bars);
+    let projects = configs
+        .iter()
+        .zip(&bars)
+        .zip(scaffolded)
+        .map(|((config, bar), result)| {
+            let result = match result {
+                Ok(report) if config.install_deps || config.postgen.is_some() => {
+                    bar.set_message(terminal_text("📦 Installing..."));
+                    // The install prints its own steps; keep the bars out of the way
+                    let result = multi.suspend(|| finish_scaffolded(base_dir, config, report));
+                    finish_bar(bar, &result);
+                    result
+                }
+                other => other,
+            };
+            ProjectOutcome {
+                name: config.name.clone(),
+                result,
+            }
+        })
+        .collect();
+
+    MultiProjectReport { projects }
+}
+
+/// Writes the files of each of `configs`, `jobs` at a time, in the order of
+/// `configs`, showing each one's steps on its bar in `bars`. The warnings are
+/// the ones for the full config, not for the install-less [`scaffold_config`]
+/// that's generated.
+fn scaffold_projects(
+    base_dir: &Path,
+    configs: &[ProjectConfig],
+    jobs: usize,
+    bars: &[ProgressBar],
+) -> Vec<std::result::Result<GenerationReport, String>> {
+    let total = configs.len();
+    let jobs = jobs.clamp(1, total.max(1));
+    let next = AtomicUsize::new(0);
+    let scaffolded = Mutex::new(vec![None; total]);
+
+    say!("\n🏗️  Creating {} projects, {} at a time", total, jobs);
+    // Each project's step lines would interleave; the bars show them instead
+    set_announce(false);
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(config) = configs.get(index) else {
+                    break;
+                };
+                let bar = &bars[index];
+                let warnings = project_warnings(base_dir, config);
+                let result = generate_with_warnings(
+                    base_dir,
+                    &scaffold_config(config),
+                    warnings,
+                    Some(bar.clone()),
+                )
+                .map_err(|err| format!("{:#}", err));
+
+                match &result {
+                    Ok(_) if config.install_deps || config.postgen.is_some() => {
+                        bar.set_message(terminal_text("⏳ Waiting to install..."));
+                    }
+                    _ => finish_bar(bar, &result),
+                }
+                scaffolded.lock().unwrap()[index] = Some(result);
+            });
+        }
+    });
+    set_announce(true);
+
+    scaffolded
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every project is scaffolded"))
+        .collect()
+}
+
+/// Runs the install and the steps after it for a project
+/// [`generate_projects`] has scaffolded.
+fn finish_scaffolded(
+    base_dir: &Path,
+    config: &ProjectConfig,
+    mut report: GenerationReport,
+) -> std::result::Result<GenerationReport, String> {
+    let started = Instant::now();
+    println!("\n{}", config.name.yellow());
+
+    let mut progress = Progress::new(plan_length(config) - plan_length(&scaffold_config(config)));
+    finish_project(
+        &base_dir.join(&config.name),
+        config,
+        &mut progress,
+        &mut report.warnings,
+    )
+    .map_err(|err| format!("{:#}", err))?;
 
-    for dir in DIRECTORIES {
+    report.progress.extend(progress.lines);
+    report.phases.extend(progress.phases);
+    report.elapsed += started.elapsed();
+    Ok(report)
+}
+
+/// A file generating the project would write, relative to its root.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedFile {
+    pub path: String,
+    pub bytes: u64,
+    /// `None` for binary files such as fonts
+    pub content: Option<String>,
+}
+
+/// Everything generating `config` would write, for `--dry-run-json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct GenerationPlan {
+    pub config: ProjectConfig,
+    pub warnings: Vec<String>,
+    pub directories: Vec<String>,
+    pub files: Vec<PlannedFile>,
+}
+
+/// Refuses an `--additive` run that would overwrite existing files, listing them.
+fn check_additive(
+    project_path: &Path,
+    config: &ProjectConfig,
+    local_font: bool,
+    use_git: bool,
+) -> Result<()> {
+    let mut planned: Vec<String> = plan_project(config)?
+        .files
+        .into_iter()
+        .map(|file| file.path)
+        .collect();
+    if local_font {
+        planned.push(LOCAL_FONT_PATH.to_string());
+    }
+
+    let collisions: Vec<String> = planned
+        .into_iter()
+        .filter(|path| project_path.join(path).exists())
+        .collect();
+    if !collisions.is_empty() {
+        anyhow::bail!(
+            "--additive won't overwrite {} existing file(s) in '{}':\n   {}",
+            collisions.len(),
+            config.name,
+            collisions.join("\n   ")
+        );
+    }
+
+    if use_git && project_path.join(".git").exists() {
+        anyhow::bail!(
+            "'{}' is already a git repository; leave out --git to add the project to it",
+            config.name
+        );
+    }
+    Ok(())
+}
+
+/// Collects the files the generation steps produce, without writing
+/// anything. Nothing is installed, committed or downloaded either.
+pub fn plan_project(config: &ProjectConfig) -> Result<GenerationPlan> {
+    let mut planned: Vec<(String, FileContent)> = Vec::new();
+    for step in generation_steps(config) {
+        for (path, content) in (step.run)(config)? {
+            match planned
+                .iter_mut()
+                .find(|(planned_path, _)| *planned_path == path)
+            {
+                // Like on disk: assets never replace a generated file
+                Some(_) if matches!(content, FileContent::Asset(_)) => {}
+                Some(existing) => existing.1 = content,
+                None => planned.push((path, content)),
+            }
+        }
+    }
+    planned.sort_by(|(a, _), (b, _)| Path::new(a).cmp(Path::new(b)));
+
+    let mut directories: Vec<&Path> = config
+        .directories()
+        .into_iter()
+        .map(Path::new)
+        .chain(
+            planned
+                .iter()
+                .filter_map(|(path, _)| Path::new(path).parent()),
+        )
+        .flat_map(Path::ancestors)
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .collect();
+    directories.sort();
+    directories.dedup();
+
+    Ok(GenerationPlan {
+        config: config.clone(),
+        warnings: option_warnings(config)
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        directories: directories
+            .iter()
+            .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+            .collect(),
+        files: planned
+            .into_iter()
+            .map(|(path, content)| {
+                let bytes = content.bytes();
+                PlannedFile {
+                    bytes: bytes.len() as u64,
+                    content: String::from_utf8(bytes.to_vec()).ok(),
+                    path,
+                }
+            })
+            .collect(),
+    })
+}
+
+fn create_directories(project_path: &Path, config: &ProjectConfig) -> Result<()> {
+    for dir in config.directories() {
         let dir_path = project_path.join(dir);
         fs::create_dir_all(&dir_path)?;
-        println!("   Created: {}", dir.green());
+        announce(&format!("   Created: {}", dir.green()));
     }
 
     Ok(())
 }
 
-fn create_files(project_path: &Path, config: &ProjectConfig) -> Result<()> {
-    println!("{}", "📝 Creating project files...".blue());
+fn create_files(
+    project_path: &Path,
+    config: &ProjectConfig,
+    progress: &mut Progress,
+) -> Result<()> {
+    for step in generation_steps(config) {
+        progress.advance(&format!("📝 {}", step.feature));
+        write_files(project_path, &(step.run)(config)?)?;
+    }
 
-    create_package_json(project_path, config)?;
-    create_tsconfig(project_path)?;
-    create_postcss_config(project_path)?;
-    create_next_config(project_path)?;
-    create_eslint_config(project_path)?;
-    create_gitignore(project_path)?;
-    create_npmrc(project_path)?;
-    create_app_layout(project_path, &config.name, config)?;
-    create_app_page(project_path, &config.name)?;
-    create_globals_css(project_path)?;
-    create_button_component(project_path)?;
+    Ok(())
+}
 
-    if config.use_react_query {
-        create_query_provider(project_path)?;
-        create_api_client(project_path)?;
-        create_example_hooks(project_path)?;
+fn create_files_with_commits(
+    project_path: &Path,
+    config: &ProjectConfig,
+    progress: &mut Progress,
+) -> Result<()> {
+    let steps = generation_steps(config);
+    for (index, step) in steps.iter().enumerate() {
+        progress.advance(&format!("📝 {}", step.feature));
+        write_files(project_path, &(step.run)(config)?)?;
+
+        let is_last_of_feature = steps
+            .get(index + 1)
+            .is_none_or(|next| next.feature != step.feature);
+        if is_last_of_feature {
+            commit_all(project_path, &feature_commit_message(step.feature))?;
+        }
     }
 
-    create_readme(project_path, &config.name)?;
+    Ok(())
+}
+
+/// Runs the linter and `tsc --noEmit` in the installed project. Failures are
+/// returned as warnings, or as an error with `--strict-install`.
+pub fn verify_project(
+    runner: &dyn CommandRunner,
+    project_path: &Path,
+    config: &ProjectConfig,
+) -> Result<Vec<Warning>> {
+    let package_manager = config.package_manager;
+    let checks = [
+        ("Lint", package_manager.script_args("lint")),
+        (
+            "Type check",
+            package_manager.exec_args("tsc", &["--noEmit"]),
+        ),
+    ];
+
+    let mut warnings = Vec::new();
+    for (name, args) in checks {
+        let args: Vec<&str> = args.iter().map(String::as_str).collect();
+        let status = runner.run(package_manager.command(), &args, project_path)?;
+        if status.success() {
+            say!("{}", format!("✅ {} passed", name).green());
+            continue;
+        }
+
+        let message = format!(
+            "{} failed in the generated project ({} {})",
+            name,
+            package_manager.command(),
+            args.join(" ")
+        );
+        if config.strict_install {
+            anyhow::bail!("{} (--strict-install)", message);
+        }
+        say!("{}", format!("❌ {}", message).red());
+        warnings.push(Warning::new(message));
+    }
+
+    Ok(warnings)
+}
+
+/// Runs the package manager's audit in the installed project, printing its
+/// report. Findings fail generation when `--audit-level` is set and are a
+/// warning otherwise.
+pub fn audit_dependencies(
+    runner: &dyn CommandRunner,
+    project_path: &Path,
+    config: &ProjectConfig,
+) -> Result<Vec<Warning>> {
+    let package_manager = config.package_manager;
+    // option_warnings already explains the skip
+    let Some(args) = package_manager.audit_args(config.audit_level) else {
+        return Ok(Vec::new());
+    };
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let status = runner.run(package_manager.command(), &args, project_path)?;
+    if status.success() {
+        say!("{}", "✅ No known vulnerabilities".green());
+        return Ok(Vec::new());
+    }
+
+    let command = format!("{} {}", package_manager.command(), args.join(" "));
+    if let Some(level) = config.audit_level {
+        anyhow::bail!(
+            "The dependency audit found {} or higher severity vulnerabilities ({})",
+            level.as_str(),
+            command
+        );
+    }
+    let message = format!(
+        "The dependency audit reported vulnerabilities ({})",
+        command
+    );
+    say!("{}", format!("⚠️  {}", message).yellow());
+    Ok(vec![Warning::new(message)])
+}
+
+/// Runs the `--postgen` shell command in the project directory with its
+/// output streamed to the terminal.
+pub fn run_postgen(runner: &dyn CommandRunner, project_path: &Path, command: &str) -> Result<()> {
+    let (shell, flag) = if cfg!(windows) {
+        ("cmd", "/C")
+    } else {
+        ("sh", "-c")
+    };
+    println!("   Running: {}", command.green());
 
+    let status = runner.run(shell, &[flag, command], project_path)?;
+    if !status.success() {
+        anyhow::bail!("The --postgen command `{}` exited with {}", command, status);
+    }
     Ok(())
 }
+
+/// Runs the `dev` script in the generated project in the foreground and opens the
+/// app in the browser. Returns when the dev server exits.
+pub fn start_dev_server(
+    runner: &dyn CommandRunner,
+    project_path: &Path,
+    config: &ProjectConfig,
+) -> Result<()> {
+    let url = config.dev_url();
+    let package_manager = config.package_manager;
+    let dev_args = package_manager.dev_args(config.dev_port());
+    let dev_args: Vec<&str> = dev_args.iter().map(String::as_str).collect();
+
+    say!(
+        "\n{}",
+        format!(
+            "🚀 Starting dev server at {} (press Ctrl+C to stop)...",
+            url
+        )
+        .blue()
+    );
+    runner.open_url(&url)?;
+
+    let status = runner.run(package_manager.command(), &dev_args, project_path)?;
+    if !status.success() {
+        anyhow::bail!("Dev server exited with {}", status);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{AuditLevel, PackageManager};
+    use crate::runner::{wait_with_timeout, ChildProcess};
+    use std::cell::RefCell;
+    use std::path::PathBuf;
+    use std::process::{ExitStatus, Output};
+    use std::time::Duration;
+
+    #[derive(Default)]
+    struct MockRunner {
+        commands: RefCell<Vec<(String, Vec<String>, PathBuf)>>,
+        opened: RefCell<Vec<String>>,
+        /// Every command exits with a failure status
+        failing: bool,
+        /// Captured commands never exit on their own
+        hanging: bool,
+        /// What captured commands print
+        stdout: Vec<u8>,
+    }
+
+    /// A child that never exits until it's killed.
+    #[derive(Default)]
+    struct HangingChild {
+        killed: bool,
+    }
+
+    impl ChildProcess for HangingChild {
+        fn try_wait(&mut self) -> std::io::Result<Option<ExitStatus>> {
+            Ok(None)
+        }
+
+        fn kill(&mut self) -> std::io::Result<()> {
+            self.killed = true;
+            Ok(())
+        }
+    }
+
+    #[cfg(unix)]
+    fn failed_status() -> ExitStatus {
+        use std::os::unix::process::ExitStatusExt;
+        ExitStatus::from_raw(1 << 8)
+    }
+
+    #[cfg(windows)]
+    fn failed_status() -> ExitStatus {
+        use std::os::windows::process::ExitStatusExt;
+        ExitStatus::from_raw(1)
+    }
+
+    impl CommandRunner for MockRunner {
+        fn run(&self, program: &str, args: &[&str], cwd: &Path) -> Result<ExitStatus> {
+            self.commands.borrow_mut().push((
+                program.to_string(),
+                args.iter().map(|arg| arg.to_string()).collect(),
+                cwd.to_path_buf(),
+            ));
+            if self.failing {
+                return Ok(failed_status());
+            }
+            Ok(ExitStatus::default())
+        }
+
+        fn output(
+            &self,
+            program: &str,
+            args: &[&str],
+            cwd: &Path,
+            timeout: Option<Duration>,
+        ) -> Result<Output> {
+            if self.hanging {
+                self.commands.borrow_mut().push((
+                    program.to_string(),
+                    args.iter().map(|arg| arg.to_string()).collect(),
+                    cwd.to_path_buf(),
+                ));
+                let timeout = timeout.expect("a hanging command needs a timeout");
+                let mut child = HangingChild::default();
+                let err = wait_with_timeout(&mut child, program, timeout).unwrap_err();
+                assert!(
+                    child.killed,
+                    "the hanging child should be killed on timeout"
+                );
+                return Err(err);
+            }
+            Ok(Output {
+                status: self.run(program, args, cwd)?,
+                stdout: self.stdout.clone(),
+                stderr: Vec::new(),
+            })
+        }
+
+        fn open_url(&self, url: &str) -> Result<()> {
+            self.opened.borrow_mut().push(url.to_string());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_start_dev_server_runs_dev_in_project_with_port() {
+        let runner = MockRunner::default();
+        let config = ProjectConfig::builder("open-app").port(4321).build();
+
+        start_dev_server(&runner, Path::new("open-app"), &config).unwrap();
+
+        let commands = runner.commands.borrow();
+        assert_eq!(commands.len(), 1);
+        let (program, args, cwd) = &commands[0];
+        assert_eq!(program, "pnpm");
+        assert_eq!(args, &["dev", "--port", "4321"]);
+        assert_eq!(cwd, Path::new("open-app"));
+        assert_eq!(*runner.opened.borrow(), vec!["http://localhost:4321"]);
+    }
+
+    #[test]
+    fn test_start_dev_server_forwards_port_through_npm_run() {
+        let runner = MockRunner::default();
+        let config = ProjectConfig::builder("npm-app")
+            .package_manager(PackageManager::Npm)
+            .build();
+
+        start_dev_server(&runner, Path::new("npm-app"), &config).unwrap();
+
+        let commands = runner.commands.borrow();
+        let (program, args, _) = &commands[0];
+        assert_eq!(program, "npm");
+        assert_eq!(args, &["run", "dev", "--", "--port", "3000"]);
+    }
+
+    #[test]
+    fn test_verify_project_runs_lint_and_tsc_in_project() {
+        let runner = MockRunner::default();
+        let config = ProjectConfig::builder("lint-app").verify_lint(true).build();
+
+        let warnings = verify_project(&runner, Path::new("lint-app"), &config).unwrap();
+
+        assert!(warnings.is_empty());
+        let commands = runner.commands.borrow();
+        let invocations: Vec<(&str, Vec<&str>, &Path)> = commands
+            .iter()
+            .map(|(program, args, cwd)| {
+                (
+                    program.as_str(),
+                    args.iter().map(String::as_str).collect(),
+                    cwd.as_path(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            invocations,
+            [
+                ("pnpm", vec!["lint"], Path::new("lint-app")),
+                (
+                    "pnpm",
+                    vec!["exec", "tsc", "--noEmit"],
+                    Path::new("lint-app")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fetch_local_font_downloads_woff2_with_curl() {
+        let runner = MockRunner {
+            stdout: b"wOF2\0\x01font".to_vec(),
+            ..MockRunner::default()
+        };
+
+        let font = fetch_local_font(&runner).unwrap();
+        assert!(font.starts_with(b"wOF2"));
+        let commands = runner.commands.borrow();
+        assert_eq!(commands[0].0, "curl");
+        assert!(commands[0].1.contains(&LOCAL_FONT_URL.to_string()));
+    }
+
+    #[test]
+    fn test_fetch_local_font_rejects_failures_and_non_fonts() {
+        let offline = MockRunner {
+            failing: true,
+            ..MockRunner::default()
+        };
+        assert!(fetch_local_font(&offline).is_err());
+
+        let captive_portal = MockRunner {
+            stdout: b"<html>Sign in to Wi-Fi</html>".to_vec(),
+            ..MockRunner::default()
+        };
+        let err = fetch_local_font(&captive_portal).unwrap_err();
+        assert!(err.to_string().contains("not a WOFF2 font"));
+    }
+
+    #[test]
+    fn test_install_timeout_kills_hanging_install() {
+        let runner = MockRunner {
+            hanging: true,
+            ..MockRunner::default()
+        };
+        let config = ProjectConfig::builder("slow-app")
+            .install_timeout(1)
+            .build();
+
+        let err = install_dependencies(&runner, Path::new("slow-app"), &config).unwrap_err();
+        assert_eq!(err.to_string(), "`pnpm` timed out after 1s and was killed");
+        let commands = runner.commands.borrow();
+        assert_eq!(commands.len(), 1);
+        assert_eq!(commands[0].1, ["install"]);
+    }
+
+    #[test]
+    fn test_verify_project_failures_warn_or_fail_under_strict_install() {
+        let runner = MockRunner {
+            failing: true,
+            ..MockRunner::default()
+        };
+        let lenient = ProjectConfig::builder("lint-app").verify_lint(true).build();
+
+        let warnings = verify_project(&runner, Path::new("lint-app"), &lenient).unwrap();
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].message.starts_with("Lint failed"));
+
+        let strict = ProjectConfig::builder("lint-app")
+            .verify_lint(true)
+            .strict_install(true)
+            .build();
+        let err = verify_project(&runner, Path::new("lint-app"), &strict).unwrap_err();
+        assert!(err.to_string().contains("--strict-install"));
+        // Stops at the first failing check
+        assert_eq!(runner.commands.borrow().len(), 3);
+    }
+
+    #[test]
+    fn test_audit_dependencies_passes_level_and_fails_at_threshold() {
+        let runner = MockRunner::default();
+        let config = ProjectConfig::builder("audit-app")
+            .audit(true)
+            .audit_level(AuditLevel::High)
+            .build();
+
+        assert!(audit_dependencies(&runner, Path::new("audit-app"), &config)
+            .unwrap()
+            .is_empty());
+        let commands = runner.commands.borrow();
+        let (program, args, _) = &commands[0];
+        assert_eq!(program, "pnpm");
+        assert_eq!(args, &["audit", "--audit-level", "high"]);
+
+        let vulnerable = MockRunner {
+            failing: true,
+            ..Default::default()
+        };
+        let err = audit_dependencies(&vulnerable, Path::new("audit-app"), &config).unwrap_err();
+        assert!(err.to_string().contains("high or higher"));
+
+        // Without a level, findings are reported but don't fail
+        let npm = ProjectConfig::builder("audit-app")
+            .package_manager(PackageManager::Npm)
+            .audit(true)
+            .build();
+        let warnings = audit_dependencies(&vulnerable, Path::new("audit-app"), &npm).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(vulnerable.commands.borrow()[1].1, ["audit"]);
+    }
+
+    #[test]
+    fn test_progress_counts_up_to_plan_length() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = ProjectConfig::builder("progress-app")
+            .install_deps(false)
+            .husky(true)
+            .build();
+
+        let report = generate_project_in(temp_dir.path(), &config).unwrap();
+
+        let total = plan_length(&config);
+        assert_eq!(report.progress.len(), total);
+        for (index, line) in report.progress.iter().enumerate() {
+            assert!(
+                line.starts_with(&format!("[{}/{}] ", index + 1, total)),
+                "{}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn test_generate_refuses_filesystem_root() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = ProjectConfig::builder("/").install_deps(false).build();
+
+        let result = generate_project_in(temp_dir.path(), &config);
+
+        assert!(result.unwrap_err().to_string().contains("filesystem root"));
+    }
+
+    #[test]
+    fn test_generate_fails_before_writing_when_paths_are_too_long() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let segment = "d".repeat(250);
+        let base = (0..17).fold(temp_dir.path().to_path_buf(), |path, _| path.join(&segment));
+        let config = ProjectConfig::builder("my-app").install_deps(false).build();
+
+        let result = generate_project_in(&base, &config);
+
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("platform limit"), "{}", err);
+        assert!(!temp_dir.path().join(&segment).exists());
+    }
+
+    #[test]
+    fn test_report_collects_every_warning() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = ProjectConfig::builder("warned-app")
+            .install_deps(false)
+            .commitlint(true)
+            .git_remote("https://github.com/acme/warned-app.git")
+            .build();
+
+        let report = generate_project_in(temp_dir.path(), &config).unwrap();
+
+        let messages: Vec<&str> = report
+            .warnings
+            .iter()
+            .map(|warning| warning.message.as_str())
+            .collect();
+        assert_eq!(messages.len(), 2, "{:?}", messages);
+        assert!(messages[0].starts_with("--git-remote"));
+        assert!(messages[1].starts_with("--commitlint"));
+    }
+
+    #[test]
+    fn test_src_files_match_the_files_generated() {
+        fn walk(dir: &Path, files: &mut Vec<String>, root: &Path) {
+            for entry in fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    walk(&path, files, root);
+                } else {
+                    let relative = path.strip_prefix(root).unwrap();
+                    files.push(relative.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut config = ProjectConfig::builder("layout-app")
+            .install_deps(false)
+            .testing(true)
+            .seo(true)
+            .manifest(true)
+            .og_route(true)
+            .with_upload_example(true)
+            .server_actions(true)
+            .middleware_chain(true)
+            .api_proxy("http://localhost:8000")
+            .publishable(true)
+            .prisma(true)
+            .ratelimit(true)
+            .not_found(true)
+            .with_skeletons(true)
+            .app_states(true)
+            .scaffold_tests(true)
+            .common_hooks(true)
+            .components_barrel(true)
+            .styling(Styling::CssModules)
+            .css_structure(crate::config::CssStructure::Split)
+            .logger(Logger::Console)
+            .env_validation(crate::config::EnvValidation::Zod)
+            .observability(Observability::Otel)
+            .build();
+        config.components = vec![crate::config::Component::Table];
+        generate_project_in(temp_dir.path(), &config).unwrap();
+
+        let project_path = temp_dir.path().join("layout-app");
+        let mut written = Vec::new();
+        walk(&project_path.join("src"), &mut written, &project_path);
+        written.sort();
+        let mut listed = src_files(&config);
+        listed.sort();
+        assert_eq!(written, listed);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_postgen_runs_in_the_project_directory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+
+        run_postgen(&SystemRunner, temp_dir.path(), "touch postgen-marker").unwrap();
+        assert!(temp_dir.path().join("postgen-marker").exists());
+
+        let err = run_postgen(&SystemRunner, temp_dir.path(), "exit 3").unwrap_err();
+        assert!(err.to_string().contains("`exit 3` exited with"));
+    }
+
+    #[test]
+    fn test_biome_formatter_replaces_eslint() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = ProjectConfig::builder("biome-app")
+            .install_deps(false)
+            .formatter(Formatter::Biome)
+            .build();
+        let project = temp_dir.path().join(&config.name);
+
+        generate_project_in(temp_dir.path(), &config).unwrap();
+
+        let biome: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(project.join("biome.json")).unwrap()).unwrap();
+        assert_eq!(biome["linter"]["rules"]["recommended"], true);
+        assert!(!project.join(".eslintrc.json").exists());
+
+        let package: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(project.join("package.json")).unwrap())
+                .unwrap();
+        assert_eq!(package["scripts"]["lint"], "biome check .");
+        assert_eq!(package["scripts"]["format"], "biome format --write .");
+        assert_eq!(package["devDependencies"]["@biomejs/biome"], "^1.9.4");
+        assert!(package["devDependencies"].get("eslint").is_none());
+        assert!(package["devDependencies"]
+            .get("eslint-config-next")
+            .is_none());
+
+        let next_config = fs::read_to_string(project.join("next.config.ts")).unwrap();
+        assert!(next_config.contains("ignoreDuringBuilds: true"));
+    }
+
+    #[test]
+    fn test_generation_steps_group_features_contiguously() {
+        let config = ProjectConfig::builder("steps")
+            .husky(true)
+            .commitlint(true)
+            .build();
+
+        let features: Vec<&str> = generation_steps(&config)
+            .iter()
+            .map(|step| step.feature)
+            .collect();
+        let mut seen: Vec<&str> = Vec::new();
+        for feature in features {
+            if seen.last() != Some(&feature) {
+                assert!(!seen.contains(&feature), "'{}' is split", feature);
+                seen.push(feature);
+            }
+        }
+        assert_eq!(
+            seen,
+            ["base", "styling", "husky", "commitlint", "react-query"]
+        );
+    }
+
+    #[test]
+    fn test_generation_report_times_each_phase_that_ran() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = ProjectConfig::builder("timed-app")
+            .install_deps(false)
+            .trace(true)
+            .build();
+
+        let report = generate_project_in(temp_dir.path(), &config).unwrap();
+
+        let phases: Vec<&str> = report.phases.iter().map(|timing| timing.phase).collect();
+        assert_eq!(phases, ["checks", "directories", "files"]);
+        let total: Duration = report.phases.iter().map(|timing| timing.elapsed).sum();
+        assert!(total <= report.elapsed);
+    }
+
+    #[test]
+    fn test_skipped_directories_are_not_created() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = ProjectConfig::builder("lean-app")
+            .install_deps(false)
+            .skip_directory("src/fonts")
+            .skip_directory("src/assets")
+            .build();
+
+        generate_project_in(temp_dir.path(), &config).unwrap();
+
+        let project = temp_dir.path().join("lean-app");
+        assert!(!project.join("src/fonts").exists());
+        assert!(!project.join("src/assets").exists());
+        assert!(project.join("src/hooks").is_dir());
+        let readme = fs::read_to_string(project.join("README.md")).unwrap();
+        assert!(!readme.contains("fonts/"));
+    }
+
+    #[test]
+    fn test_additive_generation_refuses_collisions_and_keeps_other_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = |name: &str| {
+            ProjectConfig::builder(name)
+                .install_deps(false)
+                .additive(true)
+                .build()
+        };
+
+        let taken = temp_dir.path().join("taken");
+        fs::create_dir(&taken).unwrap();
+        fs::write(taken.join("README.md"), "# Mine\n").unwrap();
+        let err = generate_project_in(temp_dir.path(), &config("taken"))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("1 existing file(s)"), "{}", err);
+        assert!(err.ends_with("\n   README.md"), "{}", err);
+        assert_eq!(
+            fs::read_to_string(taken.join("README.md")).unwrap(),
+            "# Mine\n"
+        );
+        assert!(!taken.join("package.json").exists());
+
+        let notes = temp_dir.path().join("notes");
+        fs::create_dir(&notes).unwrap();
+        fs::write(notes.join("todo.txt"), "ship it\n").unwrap();
+        generate_project_in(temp_dir.path(), &config("notes")).unwrap();
+        assert_eq!(
+            fs::read_to_string(notes.join("todo.txt")).unwrap(),
+            "ship it\n"
+        );
+        assert!(notes.join("package.json").is_file());
+        assert!(notes.join("src/app/page.tsx").is_file());
+
+        // Without --additive an existing directory is still refused
+        let plain = ProjectConfig::builder("notes").install_deps(false).build();
+        assert!(generate_project_in(temp_dir.path(), &plain).is_err());
+    }
+
+    #[test]
+    fn test_generate_projects_reports_each_project() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let configs: Vec<ProjectConfig> = ["alpha", "beta", "gamma"]
+            .iter()
+            .map(|name| ProjectConfig::builder(*name).install_deps(false).build())
+            .collect();
+
+        let report = generate_projects(temp_dir.path(), &configs, 3);
+
+        let names: Vec<&str> = report.projects.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["alpha", "beta", "gamma"]);
+        assert_eq!(report.failures().count(), 0);
+        for name in names {
+            assert!(temp_dir.path().join(name).join("package.json").is_file());
+        }
+
+        // A failing project is reported without stopping the others
+        let report = generate_projects(
+            temp_dir.path(),
+            &[
+                ProjectConfig::builder("beta").install_deps(false).build(),
+                ProjectConfig::builder("delta").install_deps(false).build(),
+            ],
+            2,
+        );
+        let failures: Vec<&str> = report.failures().map(|p| p.name.as_str()).collect();
+        assert_eq!(failures, ["beta"]);
+        assert!(temp_dir.path().join("delta/package.json").is_file());
+    }
+
+    #[test]
+    fn test_scaffold_projects_drives_a_bar_per_project() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        fs::create_dir(temp_dir.path().join("taken")).unwrap();
+        let configs: Vec<ProjectConfig> = ["first", "taken", "third"]
+            .iter()
+            .map(|name| ProjectConfig::builder(*name).install_deps(false).build())
+            .collect();
+        let bars: Vec<ProgressBar> = configs.iter().map(|_| ProgressBar::hidden()).collect();
+
+        let results = scaffold_projects(temp_dir.path(), &configs, 3, &bars);
+
+        assert_eq!(results.len(), 3);
+        for index in [0, 2] {
+            assert!(results[index].is_ok());
+            assert!(bars[index].is_finished());
+            assert!(bars[index].message().contains("Done"));
+            assert_eq!(bars[index].position(), plan_length(&configs[index]) as u64);
+        }
+        assert!(results[1].is_err());
+        assert!(bars[1].message().contains("already exists"));
+    }
+
+    #[test]
+    fn test_scaffolded_projects_warn_about_the_full_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let configs: Vec<ProjectConfig> = ["audited", "verified"]
+            .iter()
+            .map(|name| {
+                ProjectConfig::builder(*name)
+                    .audit(true)
+                    .verify_lint(true)
+                    .build()
+            })
+            .collect();
+
+        let bars = [ProgressBar::hidden(), ProgressBar::hidden()];
+        for (result, bar) in scaffold_projects(temp_dir.path(), &configs, 2, &bars)
+            .into_iter()
+            .zip(&bars)
+        {
+            // Each still has its install ahead of it
+            assert!(!bar.is_finished());
+            assert!(bar.message().contains("Waiting to install"));
+            let messages: Vec<String> = result
+                .unwrap()
+                .warnings
+                .into_iter()
+                .map(|warning| warning.message)
+                .collect();
+            assert!(
+                messages
+                    .iter()
+                    .all(|message| !message.contains("--skip-install")
+                        && !message.contains("needs installed dependencies")),
+                "{:?}",
+                messages
+            );
+        }
+    }
+
+    #[test]
+    fn test_plan_project_returns_contents_without_writing_the_project() {
+        let config = ProjectConfig::builder("planned-app")
+            .install_deps(false)
+            .theme_color("#2563eb")
+            .build();
+
+        let plan = plan_project(&config).unwrap();
+
+        assert!(!Path::new("planned-app").exists());
+        let package = plan
+            .files
+            .iter()
+            .find(|file| file.path == "package.json")
+            .unwrap();
+        let content = package.content.as_deref().unwrap();
+        assert!(content.contains("\"name\": \"planned-app\""));
+        assert_eq!(package.bytes, content.len() as u64);
+        assert!(plan.directories.iter().any(|dir| dir == "src/app"));
+
+        let json: serde_json::Value = serde_json::to_value(&plan).unwrap();
+        assert_eq!(json["config"]["theme_color"], "#2563eb");
+        assert!(json["files"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|file| file["path"] == "src/app/layout.tsx" && file["content"].is_string()));
+    }
+
+    #[test]
+    fn test_plan_keeps_generated_files_over_assets() {
+        let assets = tempfile::TempDir::new().unwrap();
+        fs::write(assets.path().join("logo.svg"), "<svg>mine</svg>").unwrap();
+        fs::write(assets.path().join("robots.txt"), "User-agent: *\n").unwrap();
+        let config = ProjectConfig::builder("app")
+            .demo_asset(true)
+            .assets_from(assets.path().display().to_string())
+            .build();
+
+        let plan = plan_project(&config).unwrap();
+
+        let content = |path: &str| {
+            plan.files
+                .iter()
+                .find(|file| file.path == path)
+                .and_then(|file| file.content.clone())
+        };
+        assert!(!content("public/logo.svg").unwrap().contains("mine"));
+        assert_eq!(content("public/robots.txt").unwrap(), "User-agent: *\n");
+        assert_eq!(
+            plan.files
+                .iter()
+                .filter(|file| file.path == "public/logo.svg")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_proxy_route_is_only_generated_with_api_proxy() {
+        let has_proxy = |config: &ProjectConfig| {
+            generation_steps(config)
+                .iter()
+                .any(|step| step.feature == "api-proxy")
+        };
+
+        assert!(!has_proxy(&ProjectConfig::builder("app").build()));
+        assert!(has_proxy(
+            &ProjectConfig::builder("app")
+                .api_proxy("https://api.example.com")
+                .build()
+        ));
+    }
+}
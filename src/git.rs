@@ -0,0 +1,316 @@
+use anyhow::Result;
+use colored::Colorize;
+use std::path::Path;
+use std::process::Command;
+
+use crate::emoji::terminal_text;
+use crate::files::announce;
+
+pub fn is_git_available() -> bool {
+    which::which("git").is_ok()
+}
+
+/// Initializes a repository whose first commit lands on `branch`.
+pub fn init_repository(project_path: &Path, branch: &str) -> Result<()> {
+    let output = Command::new("git")
+        .arg("init")
+        .current_dir(project_path)
+        .output()?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to initialize git repository: {}", error.trim());
+    }
+
+    // Pointing the unborn HEAD at the branch works on gits older than
+    // `init -b` (2.28) and ignores init.defaultBranch
+    let head = Command::new("git")
+        .args(["symbolic-ref", "HEAD", &format!("refs/heads/{}", branch)])
+        .current_dir(project_path)
+        .output()?;
+
+    if !head.status.success() {
+        let error = String::from_utf8_lossy(&head.stderr);
+        anyhow::bail!(
+            "Failed to set the initial branch to '{}': {}",
+            branch,
+            error.trim()
+        );
+    }
+
+    announce(&terminal_text(
+        &"✅ Git repository initialized".green().to_string(),
+    ));
+    Ok(())
+}
+
+/// Stages everything in the working tree and commits it with `message`.
+pub fn commit_all(project_path: &Path, message: &str) -> Result<()> {
+    let add = Command::new("git")
+        .args(["add", "-A"])
+        .current_dir(project_path)
+        .output()?;
+
+    if !add.status.success() {
+        let error = String::from_utf8_lossy(&add.stderr);
+        anyhow::bail!("Failed to stage files: {}", error.trim());
+    }
+
+    let commit = Command::new("git")
+        .args(["commit", "--quiet", "-m", message])
+        .current_dir(project_path)
+        .output()?;
+
+    if !commit.status.success() {
+        let error = String::from_utf8_lossy(&commit.stderr);
+        anyhow::bail!("Failed to commit '{}': {}", message, error.trim());
+    }
+
+    println!("   Committed: {}", message.green());
+    Ok(())
+}
+
+/// Whether `url` looks like a remote git can push to: `https://host/path`,
+/// `ssh://[user@]host/path` or scp-style `user@host:path`.
+pub fn is_valid_remote_url(url: &str) -> bool {
+    if url.is_empty() || url.chars().any(char::is_whitespace) {
+        return false;
+    }
+
+    for scheme in ["https://", "ssh://"] {
+        if let Some(rest) = url.strip_prefix(scheme) {
+            return match rest.split_once('/') {
+                Some((host, path)) => !host.is_empty() && !path.is_empty(),
+                None => false,
+            };
+        }
+    }
+
+    match url.split_once(':') {
+        Some((user_host, path)) => match user_host.split_once('@') {
+            Some((user, host)) => !user.is_empty() && !host.is_empty() && !path.is_empty(),
+            None => false,
+        },
+        None => false,
+    }
+}
+
+/// Repository name at the end of a remote URL, without `.git`:
+/// `git@github.com:org/my-repo.git` gives `my-repo`. Fails unless the URL
+/// is a valid remote and the name is a usable directory slug (letters,
+/// digits, `-`, `_` and `.`, not starting with `.`).
+pub fn repo_name_from_url(url: &str) -> Result<String> {
+    if !is_valid_remote_url(url) {
+        anyhow::bail!(
+            "'{}' is not a git URL (expected https://host/org/repo or git@host:org/repo)",
+            url
+        );
+    }
+
+    let path = url.trim_end_matches('/');
+    let last = path.rsplit(['/', ':']).next().unwrap_or_default();
+    let name = last.strip_suffix(".git").unwrap_or(last);
+
+    let is_slug = !name.is_empty()
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !is_slug {
+        anyhow::bail!("Can't use '{}' from '{}' as a project name", name, url);
+    }
+    Ok(name.to_string())
+}
+
+/// The account or organization owning the repository at `url`, e.g. `acme`
+/// for `git@github.com:acme/app.git`.
+pub fn repo_owner_from_url(url: &str) -> Option<String> {
+    if !is_valid_remote_url(url) {
+        return None;
+    }
+    let path = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => url.split_once(':')?.1,
+    };
+    let (owner, _) = path.split_once('/')?;
+    (!owner.is_empty()).then(|| owner.to_string())
+}
+
+/// Whether `name` is a branch name git accepts, following the rules of
+/// `git check-ref-format --branch`.
+pub fn is_valid_branch_name(name: &str) -> bool {
+    const FORBIDDEN: &[char] = &[' ', '~', '^', ':', '?', '*', '[', '\\'];
+
+    !name.is_empty()
+        && name != "@"
+        && !name.starts_with('-')
+        && !name.starts_with('/')
+        && !name.ends_with('/')
+        && !name.ends_with('.')
+        && !name.contains("..")
+        && !name.contains("//")
+        && !name.contains("@{")
+        && !name
+            .chars()
+            .any(|c| c.is_ascii_control() || FORBIDDEN.contains(&c))
+        && name
+            .split('/')
+            .all(|part| !part.starts_with('.') && !part.ends_with(".lock"))
+}
+
+pub fn add_remote(project_path: &Path, name: &str, url: &str) -> Result<()> {
+    let output = Command::new("git")
+        .args(["remote", "add", name, url])
+        .current_dir(project_path)
+        .output()?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to add remote '{}': {}", name, error.trim());
+    }
+
+    println!("   Remote: {} -> {}", name, url.green());
+    Ok(())
+}
+
+/// Commit message used for each group of generation steps in granular mode.
+pub fn feature_commit_message(feature: &str) -> String {
+    match feature {
+        "base" => "chore: scaffold Next.js app with TypeScript and ESLint".to_string(),
+        "styling" => "style: add Tailwind CSS globals and Button component".to_string(),
+        "skeletons" => "feat: add loading skeleton for the home page".to_string(),
+        "hooks" => "feat: add common React hooks".to_string(),
+        "app-states" => "feat: add error page with retry".to_string(),
+        "not-found" => "feat: add 404 page linking back home".to_string(),
+        "demo-asset" => "feat: add public logo asset to the home page".to_string(),
+        "env" => "feat: validate environment variables at startup".to_string(),
+        "prisma" => "feat: add Prisma schema and database client".to_string(),
+        "desktop" => "feat: add Tauri desktop shell".to_string(),
+        "deploy" => "chore: add deployment config".to_string(),
+        "ratelimit" => "feat: add Upstash rate limiting".to_string(),
+        "components" => "feat: add UI primitives".to_string(),
+        "openapi" => "feat: generate API types from the OpenAPI spec".to_string(),
+        "logger" => "feat: add logger".to_string(),
+        "observability" => "feat: add instrumentation hook".to_string(),
+        "babel" => "build: compile with Babel instead of SWC".to_string(),
+        "tool-versions" => "chore: pin Node and pnpm versions".to_string(),
+        "vscode" => "chore: add VS Code tasks and Tailwind CSS data".to_string(),
+        "devcontainer" => "chore: add dev container configuration".to_string(),
+        "dependabot" => "chore: add Dependabot configuration".to_string(),
+        "ci" => "ci: add GitHub Actions workflow".to_string(),
+        "codeowners" => "chore: add CODEOWNERS".to_string(),
+        "watchman" => "chore: add watchman ignore config".to_string(),
+        "publishable" => "build: make the package publishable".to_string(),
+        "assets" => "chore: add project assets".to_string(),
+        "seo" => "feat: add sitemap and robots routes".to_string(),
+        "manifest" => "feat: add web app manifest".to_string(),
+        "api-proxy" => "feat: add development API proxy route".to_string(),
+        "middleware" => "feat: add composable middleware chain".to_string(),
+        "og-route" => "feat: add dynamic Open Graph image route".to_string(),
+        "upload" => "feat: add file upload example".to_string(),
+        "server-actions" => "feat: add server action example with typed results".to_string(),
+        "husky" => "chore: add husky pre-commit hook".to_string(),
+        "commitlint" => "chore: enforce Conventional Commits with commitlint".to_string(),
+        "react-query" => "feat: add React Query provider, API client and hooks".to_string(),
+        "testing" => "test: add Vitest with example component and API tests".to_string(),
+        other => format!("feat: add {}", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feature_commit_message_known_features() {
+        assert!(feature_commit_message("base").contains("scaffold"));
+        assert!(feature_commit_message("styling").contains("Tailwind"));
+        assert!(feature_commit_message("react-query").contains("React Query"));
+    }
+
+    #[test]
+    fn test_is_valid_branch_name() {
+        for name in ["main", "trunk", "develop", "release/1.0", "feat-x_y"] {
+            assert!(is_valid_branch_name(name), "{}", name);
+        }
+        for name in [
+            "",
+            "@",
+            "-main",
+            "/main",
+            "main/",
+            "main.",
+            "a..b",
+            "a//b",
+            "a@{1}",
+            "my branch",
+            "a~1",
+            "a^",
+            "a:b",
+            "a?",
+            "a*",
+            "a[b",
+            "a\\b",
+            ".hidden",
+            "x/.hidden",
+            "main.lock",
+            "tab\there",
+        ] {
+            assert!(!is_valid_branch_name(name), "{:?}", name);
+        }
+    }
+
+    #[test]
+    fn test_is_valid_remote_url() {
+        assert!(is_valid_remote_url("https://github.com/acme/app.git"));
+        assert!(is_valid_remote_url("ssh://git@github.com/acme/app.git"));
+        assert!(is_valid_remote_url("git@github.com:acme/app.git"));
+
+        assert!(!is_valid_remote_url(""));
+        assert!(!is_valid_remote_url("github.com/acme/app"));
+        assert!(!is_valid_remote_url("http://github.com/acme/app.git"));
+        assert!(!is_valid_remote_url("https://github.com"));
+        assert!(!is_valid_remote_url("git@github.com:"));
+        assert!(!is_valid_remote_url("https://github.com/acme/my app.git"));
+    }
+
+    #[test]
+    fn test_repo_name_from_url() {
+        assert_eq!(
+            repo_name_from_url("git@github.com:org/my-repo.git").unwrap(),
+            "my-repo"
+        );
+        assert_eq!(
+            repo_name_from_url("https://github.com/org/my-repo").unwrap(),
+            "my-repo"
+        );
+        assert_eq!(
+            repo_name_from_url("https://github.com/org/my-repo.git").unwrap(),
+            "my-repo"
+        );
+        assert_eq!(
+            repo_name_from_url("ssh://git@host/org/sub/app_2.git/").unwrap(),
+            "app_2"
+        );
+        assert_eq!(repo_name_from_url("git@host:solo.git").unwrap(), "solo");
+
+        assert!(repo_name_from_url("github.com/org/my-repo").is_err());
+
+        assert_eq!(
+            repo_owner_from_url("git@github.com:acme/app.git").as_deref(),
+            Some("acme")
+        );
+        assert_eq!(
+            repo_owner_from_url("https://github.com/acme/app").as_deref(),
+            Some("acme")
+        );
+        assert_eq!(repo_owner_from_url("git@host:solo.git"), None);
+        assert!(repo_name_from_url("https://github.com/org/.git").is_err());
+        assert!(repo_name_from_url("https://github.com/org/my%20repo.git").is_err());
+    }
+
+    #[test]
+    fn test_feature_commit_message_fallback() {
+        assert_eq!(feature_commit_message("docker"), "feat: add docker");
+    }
+}
@@ -2,10 +2,26 @@ pub mod config;
 pub mod files;
 pub mod validation;
 pub mod generator;
+pub mod git;
+pub mod runner;
+pub mod upgrade;
+pub mod usage;
+pub mod openapi;
+pub mod preflight;
+pub mod versions;
 pub mod cli;
+pub mod emoji;
 
 pub use config::*;
 pub use files::*; 
 pub use validation::*;
 pub use generator::*;
-pub use cli::*;
\ No newline at end of file
+pub use git::*;
+pub use runner::*;
+pub use upgrade::*;
+pub use usage::*;
+pub use openapi::*;
+pub use preflight::*;
+pub use versions::*;
+pub use cli::*;
+pub use emoji::*;
\ No newline at end of file
@@ -1,14 +1,143 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Parser;
 
-use aui_next_generator::{Cli, get_project_config, generate_project, print_success_message};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use aui_next_generator::{
+    append_usage_log, apply_color_choice, environment_checks, generate_project, generate_projects,
+    get_project_config, multi_project_warnings, plan_project, print_config_report,
+    print_multi_project_report, print_phase_timings, print_preflight_report, print_success_message,
+    print_warnings, review_config_change, run_checks, start_dev_server, upgrade_project,
+    upgrade_project_with, validate_config, validate_options, Cli, Command, ConfigFormat,
+    ProjectConfig, SystemRunner, Warning, MIN_NODE_VERSION,
+};
 
 fn main() -> Result<()> {
     let args = Cli::parse();
-    let config = get_project_config(args)?;
-    
-    generate_project(&config)?;
+    apply_color_choice(&args);
+
+    if let Some(Command::Upgrade { path, show_diff }) = &args.command {
+        let path = path.clone().unwrap_or_else(|| PathBuf::from("."));
+        if *show_diff {
+            upgrade_project_with(&path, review_config_change)?;
+        } else {
+            upgrade_project(&path)?;
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Check {
+        package_manager,
+        minimum_node,
+    }) = &args.command
+    {
+        let checks = environment_checks(
+            Path::new("."),
+            *package_manager,
+            minimum_node.unwrap_or(MIN_NODE_VERSION),
+        );
+        let report = run_checks(&checks);
+        print_preflight_report(&report);
+        if !report.passed() {
+            anyhow::bail!("Environment check failed");
+        }
+        return Ok(());
+    }
+
+    if let Some(Command::Validate { config }) = &args.command {
+        let format = ConfigFormat::from_path(config)?;
+        let content = fs::read_to_string(config)
+            .with_context(|| format!("Could not read config '{}'", config.display()))?;
+        let report = validate_config(&content, format);
+        print_config_report(config, &report);
+        if !report.is_valid() {
+            anyhow::bail!("{} is not a valid config", config.display());
+        }
+        return Ok(());
+    }
+
+    let config = get_project_config(&args)?;
+    if args.print_config {
+        print!("{}", config.to_format(args.config_format)?);
+        return Ok(());
+    }
+
+    let mut warnings = validate_options(&config, &args)?;
+
+    if args.dry_run_json {
+        let mut plan = plan_project(&config)?;
+        plan.warnings
+            .extend(warnings.iter().map(ToString::to_string));
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+        return Ok(());
+    }
+
+    if !args.more_names.is_empty() {
+        let mut configs = vec![config.clone()];
+        for name in &args.more_names {
+            if configs.iter().any(|other| &other.name == name) {
+                anyhow::bail!("Project '{}' is listed twice", name);
+            }
+            let config = ProjectConfig {
+                name: name.clone(),
+                ..config.clone()
+            };
+            validate_options(&config, &args)?;
+            configs.push(config);
+        }
+        if config.open_dev_server {
+            warnings.push(Warning::new(
+                "--open starts a dev server for a single project; ignoring it",
+            ));
+        }
+
+        let report = generate_projects(Path::new("."), &configs, args.jobs);
+        print_multi_project_report(&report);
+        if config.trace {
+            for project in &report.projects {
+                if let Ok(created) = &project.result {
+                    print_phase_timings(&project.name, &created.phases);
+                }
+            }
+        }
+        warnings.extend(multi_project_warnings(&report));
+
+        if let Some(log) = &args.usage_log {
+            for config in &configs {
+                if let Err(err) = append_usage_log(log, config) {
+                    warnings.push(Warning::new(format!("Could not write usage log: {}", err)));
+                    break;
+                }
+            }
+        }
+
+        print_warnings(&warnings);
+        let failed = report.failures().count();
+        if failed > 0 {
+            anyhow::bail!("{} of {} projects failed", failed, configs.len());
+        }
+        return Ok(());
+    }
+
+    let report = generate_project(&config)?;
+    warnings.extend(report.warnings);
     print_success_message(&config);
+    if config.trace {
+        print_phase_timings(&config.name, &report.phases);
+    }
+
+    if let Some(log) = &args.usage_log {
+        if let Err(err) = append_usage_log(log, &config) {
+            warnings.push(Warning::new(format!("Could not write usage log: {}", err)));
+        }
+    }
+
+    print_warnings(&warnings);
+
+    if config.open_dev_server && config.install_deps {
+        start_dev_server(&SystemRunner, Path::new(&config.name), &config)?;
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
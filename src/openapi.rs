@@ -0,0 +1,308 @@
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::Path;
+
+use crate::config::ProjectConfig;
+use crate::files::Files;
+
+/// Where the generated types live; the `api:types` script writes here too.
+pub const API_TYPES_PATH: &str = "src/types/api.d.ts";
+
+/// Whether `spec` is fetched over HTTP rather than read from disk.
+pub fn is_remote_spec(spec: &str) -> bool {
+    spec.starts_with("https://") || spec.starts_with("http://")
+}
+
+/// Name the spec is copied to in the project root, keeping its format.
+pub fn spec_file_name(spec: &str) -> &'static str {
+    let lower = spec.to_ascii_lowercase();
+    if lower.ends_with(".yaml") {
+        "openapi.yaml"
+    } else if lower.ends_with(".yml") {
+        "openapi.yml"
+    } else {
+        "openapi.json"
+    }
+}
+
+/// What the `api:types` script passes to openapi-typescript: the URL for
+/// remote specs, the copy in the project root otherwise.
+pub fn spec_script_source(spec: &str) -> String {
+    if is_remote_spec(spec) {
+        spec.to_string()
+    } else {
+        format!("./{}", spec_file_name(spec))
+    }
+}
+
+/// Parses an OpenAPI document. JSON is a subset of YAML, so one parser
+/// covers both formats.
+pub fn parse_spec(content: &str) -> Result<Value> {
+    let spec: Value = serde_yaml_ng::from_str(content).context("spec is not valid JSON or YAML")?;
+
+    let version = spec
+        .get("openapi")
+        .and_then(Value::as_str)
+        .context("spec has no `openapi` version field")?;
+    if !version.starts_with("3.") {
+        anyhow::bail!(
+            "OpenAPI {} is not supported; openapi-typescript needs a 3.x spec",
+            version
+        );
+    }
+    if !spec.get("info").is_some_and(Value::is_object) {
+        anyhow::bail!("spec has no `info` object");
+    }
+
+    Ok(spec)
+}
+
+/// Reads and validates a local spec.
+pub fn load_spec(path: &Path) -> Result<Value> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Could not read OpenAPI spec '{}'", path.display()))?;
+    parse_spec(&content).with_context(|| format!("Invalid OpenAPI spec '{}'", path.display()))
+}
+
+/// TypeScript for a schema object, e.g. `{ id: number; tag?: string }`.
+fn schema_type(schema: &Value) -> String {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        return match reference.strip_prefix("#/components/schemas/") {
+            Some(name) => format!("components['schemas']['{}']", name),
+            None => "unknown".to_string(),
+        };
+    }
+
+    let combined = |key: &str, separator: &str| {
+        schema.get(key).and_then(Value::as_array).map(|variants| {
+            variants
+                .iter()
+                .map(schema_type)
+                .collect::<Vec<_>>()
+                .join(separator)
+        })
+    };
+
+    let base = if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        values
+            .iter()
+            .map(|value| value.to_string().replace('"', "'"))
+            .collect::<Vec<_>>()
+            .join(" | ")
+    } else if let Some(all) = combined("allOf", " & ") {
+        all
+    } else if let Some(any) = combined("oneOf", " | ").or_else(|| combined("anyOf", " | ")) {
+        any
+    } else {
+        match schema.get("type").and_then(Value::as_str) {
+            Some("string") => "string".to_string(),
+            Some("integer") | Some("number") => "number".to_string(),
+            Some("boolean") => "boolean".to_string(),
+            Some("array") => {
+                let items = schema
+                    .get("items")
+                    .map(schema_type)
+                    .unwrap_or_else(|| "unknown".to_string());
+                if items.contains(' ') {
+                    format!("({})[]", items)
+                } else {
+                    format!("{}[]", items)
+                }
+            }
+            Some("object") | None if schema.get("properties").is_some() => object_type(schema),
+            Some("object") => match schema.get("additionalProperties") {
+                Some(Value::Object(_)) => format!(
+                    "Record<string, {}>",
+                    schema_type(&schema["additionalProperties"])
+                ),
+                _ => "Record<string, unknown>".to_string(),
+            },
+            _ => "unknown".to_string(),
+        }
+    };
+
+    if schema.get("nullable").and_then(Value::as_bool) == Some(true) {
+        format!("{} | null", base)
+    } else {
+        base
+    }
+}
+
+fn object_type(schema: &Value) -> String {
+    let empty = Map::new();
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    if properties.is_empty() {
+        return "Record<string, never>".to_string();
+    }
+
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let fields = properties
+        .iter()
+        .map(|(name, property)| {
+            let optional = if required.contains(&name.as_str()) {
+                ""
+            } else {
+                "?"
+            };
+            format!(
+                "{}{}: {}",
+                property_key(name),
+                optional,
+                schema_type(property)
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("; ");
+    format!("{{ {} }}", fields)
+}
+
+/// Quotes property names that aren't valid identifiers, e.g. `'created-at'`.
+fn property_key(name: &str) -> String {
+    let is_identifier = name
+        .chars()
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_' || first == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        format!("'{}'", name.replace('\'', "\\'"))
+    }
+}
+
+/// `src/types/api.d.ts` for `spec`, shaped like openapi-typescript's
+/// `components` export plus one alias per schema.
+pub fn types_from_spec(spec: &Value, source: &str) -> String {
+    let empty = Map::new();
+    let schemas = spec
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+
+    let mut content = format!(
+        "// Generated by aui-next-gen from {}.\n// Regenerate with the `api:types` script (openapi-typescript) after the spec changes.\n\nexport interface components {{\n  schemas: {{\n",
+        source
+    );
+    for (name, schema) in schemas {
+        content.push_str(&format!(
+            "    {}: {}\n",
+            property_key(name),
+            schema_type(schema)
+        ));
+    }
+    content.push_str("  }\n}\n");
+
+    for name in schemas.keys().filter(|name| property_key(name) == **name) {
+        content.push_str(&format!(
+            "\nexport type {} = components['schemas']['{}']\n",
+            name, name
+        ));
+    }
+    content
+}
+
+/// Placeholder for remote specs until the `api:types` script has run.
+fn pending_types(source: &str) -> String {
+    format!(
+        "// Types for {} are generated by the `api:types` script (openapi-typescript).\n// Run it once dependencies are installed.\n\nexport interface components {{\n  schemas: Record<string, never>\n}}\n",
+        source
+    )
+}
+
+/// `src/types/api.d.ts` for `--openapi`, plus a copy of the spec when it is a
+/// local file.
+pub fn openapi_types_files(config: &ProjectConfig) -> Result<Files> {
+    let mut files = Files::new();
+    let Some(spec) = &config.openapi else {
+        return Ok(files);
+    };
+
+    if is_remote_spec(spec) {
+        files.text(API_TYPES_PATH, &pending_types(spec));
+        return Ok(files);
+    }
+
+    let content = fs::read_to_string(spec)
+        .with_context(|| format!("Could not read OpenAPI spec '{}'", spec))?;
+    let parsed =
+        parse_spec(&content).with_context(|| format!("Invalid OpenAPI spec '{}'", spec))?;
+    let file_name = spec_file_name(spec);
+
+    files.text(file_name, &content);
+    files.text(API_TYPES_PATH, &types_from_spec(&parsed, file_name));
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r#"openapi: 3.0.3
+info:
+  title: Petstore
+  version: 1.0.0
+paths: {}
+components:
+  schemas:
+    Pet:
+      type: object
+      required: [id, name]
+      properties:
+        id:
+          type: integer
+        name:
+          type: string
+        tag:
+          type: string
+          nullable: true
+        status:
+          type: string
+          enum: [available, sold]
+    Pets:
+      type: array
+      items:
+        $ref: '#/components/schemas/Pet'
+"#;
+
+    #[test]
+    fn test_types_from_spec_maps_schemas() {
+        let spec = parse_spec(PETSTORE).unwrap();
+
+        let types = types_from_spec(&spec, "openapi.yaml");
+
+        assert!(types.contains(
+            "    Pet: { id: number; name: string; status?: 'available' | 'sold'; tag?: string | null }\n"
+        ));
+        assert!(types.contains("    Pets: components['schemas']['Pet'][]\n"));
+        assert!(types.contains("export type Pet = components['schemas']['Pet']\n"));
+    }
+
+    #[test]
+    fn test_parse_spec_rejects_non_openapi_documents() {
+        assert!(parse_spec("name: not a spec").is_err());
+        assert!(parse_spec("{ \"swagger\": \"2.0\", \"info\": {} }").is_err());
+        assert!(parse_spec("openapi: 2.0\ninfo: {}").is_err());
+        assert!(parse_spec("{ \"openapi\": \"3.1.0\", \"info\": {} }").is_ok());
+    }
+
+    #[test]
+    fn test_spec_script_source() {
+        assert_eq!(spec_script_source("../api/spec.YML"), "./openapi.yml");
+        assert_eq!(spec_script_source("spec.json"), "./openapi.json");
+        assert_eq!(
+            spec_script_source("https://api.example.com/openapi.json"),
+            "https://api.example.com/openapi.json"
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use crate::config::PackageManager;
+use crate::emoji::say;
+use crate::validation::{installed_node_version, is_node_version_at_least, parse_node_version};
+
+/// Free space below which the disk check warns: a scaffold with its
+/// node_modules usually lands between 300 and 500 MB.
+pub const MIN_FREE_SPACE_MB: u64 = 500;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    Pass,
+    /// An optional check failed
+    Warn,
+    /// A required check failed
+    Fail,
+}
+
+/// One environment check run by `aui-next-gen check`. `run` returns a short
+/// detail such as a version on success.
+pub struct Check {
+    pub name: String,
+    /// A failure makes the whole preflight fail rather than warn
+    pub required: bool,
+    pub run: Box<dyn Fn() -> Result<String>>,
+}
+
+impl Check {
+    pub fn new(
+        name: impl Into<String>,
+        required: bool,
+        run: impl Fn() -> Result<String> + 'static,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            required,
+            run: Box::new(run),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckOutcome {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+}
+
+#[derive(Debug, Default)]
+pub struct PreflightReport {
+    pub outcomes: Vec<CheckOutcome>,
+}
+
+impl PreflightReport {
+    /// Whether every required check passed.
+    pub fn passed(&self) -> bool {
+        self.outcomes
+            .iter()
+            .all(|outcome| outcome.status != CheckStatus::Fail)
+    }
+}
+
+/// Runs every check, even after one fails, so the report is complete.
+pub fn run_checks(checks: &[Check]) -> PreflightReport {
+    let outcomes = checks
+        .iter()
+        .map(|check| {
+            let (status, detail) = match (check.run)() {
+                Ok(detail) => (CheckStatus::Pass, detail),
+                Err(err) if check.required => (CheckStatus::Fail, format!("{:#}", err)),
+                Err(err) => (CheckStatus::Warn, format!("{:#}", err)),
+            };
+            CheckOutcome {
+                name: check.name.clone(),
+                status,
+                detail,
+            }
+        })
+        .collect();
+    PreflightReport { outcomes }
+}
+
+/// The checks `generate` relies on: Node.js, the package manager, free disk
+/// space and a writable output directory.
+pub fn environment_checks(
+    dir: &Path,
+    package_manager: PackageManager,
+    minimum_node: (u32, u32),
+) -> Vec<Check> {
+    let disk_dir = dir.to_path_buf();
+    let write_dir = dir.to_path_buf();
+    vec![
+        Check::new("Node.js", true, move || node_check(minimum_node)),
+        Check::new(package_manager.command(), true, move || {
+            tool_version(package_manager.command())
+        }),
+        Check::new("Disk space", false, move || {
+            let free = free_space_mb(&disk_dir)?;
+            if free < MIN_FREE_SPACE_MB {
+                anyhow::bail!(
+                    "{} MB free; at least {} MB recommended",
+                    free,
+                    MIN_FREE_SPACE_MB
+                );
+            }
+            Ok(format!("{} MB free", free))
+        }),
+        Check::new("Writable directory", true, move || {
+            writable_check(&write_dir)
+        }),
+    ]
+}
+
+fn node_check(minimum: (u32, u32)) -> Result<String> {
+    let version = installed_node_version()?;
+    let parsed = parse_node_version(&version)?;
+    if !is_node_version_at_least(parsed, minimum) {
+        anyhow::bail!(
+            "v{} is older than the required v{}.{}",
+            version,
+            minimum.0,
+            minimum.1
+        );
+    }
+    Ok(format!("v{}", version))
+}
+
+fn tool_version(program: &str) -> Result<String> {
+    let path = which::which(program)
+        .with_context(|| format!("{} is not installed or not in PATH", program))?;
+    let output = Command::new(path).arg("--version").output()?;
+    if !output.status.success() {
+        anyhow::bail!("`{} --version` failed", program);
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Free space on the filesystem holding `dir`, from POSIX `df`.
+fn free_space_mb(dir: &Path) -> Result<u64> {
+    let output = Command::new("df")
+        .arg("-Pk")
+        .arg(dir)
+        .output()
+        .context("could not run df to measure free space")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    // Filesystem 1024-blocks Used Available Capacity Mounted-on
+    let available_kb: u64 = stdout
+        .lines()
+        .nth(1)
+        .and_then(|line| line.split_whitespace().nth(3))
+        .and_then(|field| field.parse().ok())
+        .context("could not read free space from df")?;
+    Ok(available_kb / 1024)
+}
+
+fn writable_check(dir: &Path) -> Result<String> {
+    let probe = dir.join(format!(".aui-next-gen-check-{}", std::process::id()));
+    fs::write(&probe, b"")
+        .with_context(|| format!("cannot create files in '{}'", dir.display()))?;
+    fs::remove_file(&probe)?;
+    Ok(dir.display().to_string())
+}
+
+pub fn print_preflight_report(report: &PreflightReport) {
+    say!("\n🩺 Environment check");
+    for outcome in &report.outcomes {
+        let line = format!("{}: {}", outcome.name, outcome.detail);
+        match outcome.status {
+            CheckStatus::Pass => say!("   ✅ {}", line),
+            CheckStatus::Warn => say!("   ⚠️  {}", line.yellow()),
+            CheckStatus::Fail => say!("   ❌ {}", line.red()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_checks_fails_only_on_required_failures() {
+        let checks = vec![
+            Check::new("node", true, || Ok("v20.11.1".to_string())),
+            Check::new("disk", false, || anyhow::bail!("120 MB free")),
+        ];
+        let report = run_checks(&checks);
+        assert!(report.passed());
+        assert_eq!(report.outcomes[0].status, CheckStatus::Pass);
+        assert_eq!(report.outcomes[0].detail, "v20.11.1");
+        assert_eq!(report.outcomes[1].status, CheckStatus::Warn);
+
+        let checks = vec![
+            Check::new("pnpm", true, || anyhow::bail!("pnpm is not installed")),
+            Check::new("writable", true, || Ok(".".to_string())),
+        ];
+        let report = run_checks(&checks);
+        assert!(!report.passed());
+        // Later checks still run after a failure
+        assert_eq!(report.outcomes.len(), 2);
+        assert_eq!(report.outcomes[0].status, CheckStatus::Fail);
+        assert_eq!(report.outcomes[0].detail, "pnpm is not installed");
+        assert_eq!(report.outcomes[1].status, CheckStatus::Pass);
+    }
+
+    #[test]
+    fn test_writable_check_reports_unwritable_dirs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        assert!(writable_check(temp_dir.path()).is_ok());
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
+        assert!(writable_check(&temp_dir.path().join("missing")).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use std::io::{self, Read};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Output, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait before opening the browser, giving `next dev` time to
+/// start listening.
+const BROWSER_OPEN_DELAY: Duration = Duration::from_secs(3);
+
+/// How often a command with a timeout is checked for having exited.
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Abstraction over spawning external tools so the steps that shell out can
+/// be tested without running them.
+pub trait CommandRunner {
+    /// Runs `program` attached to the terminal in `cwd` until it exits.
+    fn run(&self, program: &str, args: &[&str], cwd: &Path) -> Result<ExitStatus>;
+
+    /// Runs `program` in `cwd` capturing its output. With a `timeout`, the
+    /// process is killed once it elapses and a timeout error is returned.
+    fn output(
+        &self,
+        program: &str,
+        args: &[&str],
+        cwd: &Path,
+        timeout: Option<Duration>,
+    ) -> Result<Output>;
+
+    /// Opens `url` in the default browser without blocking the caller.
+    fn open_url(&self, url: &str) -> Result<()>;
+}
+
+/// Runs commands for real.
+pub struct SystemRunner;
+
+impl CommandRunner for SystemRunner {
+    fn run(&self, program: &str, args: &[&str], cwd: &Path) -> Result<ExitStatus> {
+        let status = Command::new(program).args(args).current_dir(cwd).status()?;
+        Ok(status)
+    }
+
+    fn output(
+        &self,
+        program: &str,
+        args: &[&str],
+        cwd: &Path,
+        timeout: Option<Duration>,
+    ) -> Result<Output> {
+        let mut command = Command::new(program);
+        command.args(args).current_dir(cwd);
+        let Some(timeout) = timeout else {
+            return Ok(command.output()?);
+        };
+
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        // Drain both pipes while waiting so a chatty install can't fill one
+        // and block forever
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+
+        let status = wait_with_timeout(&mut child, program, timeout)?;
+        Ok(Output {
+            status,
+            stdout: stdout.join().unwrap_or_default(),
+            stderr: stderr.join().unwrap_or_default(),
+        })
+    }
+
+    fn open_url(&self, url: &str) -> Result<()> {
+        let url = url.to_string();
+        thread::spawn(move || {
+            thread::sleep(BROWSER_OPEN_DELAY);
+            let _ = open::that_detached(url);
+        });
+        Ok(())
+    }
+}
+
+/// The parts of a spawned process [`wait_with_timeout`] needs, so the
+/// timeout can be tested without a real child.
+pub trait ChildProcess {
+    fn try_wait(&mut self) -> io::Result<Option<ExitStatus>>;
+    fn kill(&mut self) -> io::Result<()>;
+}
+
+impl ChildProcess for Child {
+    fn try_wait(&mut self) -> io::Result<Option<ExitStatus>> {
+        Child::try_wait(self)
+    }
+
+    fn kill(&mut self) -> io::Result<()> {
+        Child::kill(self)?;
+        // Reap it so it doesn't linger as a zombie
+        Child::wait(self).map(|_| ())
+    }
+}
+
+/// Waits for `child` to exit, killing it and failing once `timeout` elapses.
+pub fn wait_with_timeout(
+    child: &mut impl ChildProcess,
+    program: &str,
+    timeout: Duration,
+) -> Result<ExitStatus> {
+    // A timeout too large to represent never fires
+    let deadline = Instant::now().checked_add(timeout);
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(status);
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            child.kill()?;
+            anyhow::bail!(
+                "`{}` timed out after {}s and was killed",
+                program,
+                timeout.as_secs()
+            );
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn drain(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+    thread::spawn(move || {
+        let mut buffer = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buffer);
+        }
+        buffer
+    })
+}
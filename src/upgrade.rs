@@ -0,0 +1,360 @@
+use anyhow::Result;
+use colored::Colorize;
+use similar::TextDiff;
+use std::fs;
+use std::path::Path;
+
+use crate::config::{DeployTarget, Desktop, Formatter, Logger, Observability, ProjectConfig};
+use crate::emoji::say;
+use crate::files::{
+    atomic_write, eslint_config_content, gitignore_content, next_config_content,
+    postcss_config_content, tsconfig_content, TSCONFIG_COMPILER_OPTIONS,
+};
+
+type Template = fn(&ProjectConfig) -> String;
+
+/// Config files `upgrade` is allowed to regenerate. Everything under `src/`
+/// belongs to the user and is never touched.
+pub static UPGRADE_FILES: &[(&str, Template)] = &[
+    ("tsconfig.json", tsconfig_content),
+    (".eslintrc.json", eslint_config_content),
+    ("next.config.ts", next_config_content),
+    ("postcss.config.mjs", postcss_config_content),
+    (".gitignore", gitignore_content),
+];
+
+/// Marker comments around a generator-owned region. `upgrade` rewrites what's
+/// between them and keeps the rest of the file as the user left it.
+pub const MANAGED_BEGIN: &str = "aui:managed begin";
+pub const MANAGED_END: &str = "aui:managed end";
+
+/// Line comment syntax for a config file; JSON files have none.
+fn comment_prefix(relative_path: &str) -> Option<&'static str> {
+    if relative_path.ends_with(".json") {
+        None
+    } else if relative_path == ".gitignore" {
+        Some("#")
+    } else {
+        Some("//")
+    }
+}
+
+/// `content` between managed markers. JSON files are returned unchanged.
+pub fn with_managed_markers(relative_path: &str, content: &str) -> String {
+    let Some(prefix) = comment_prefix(relative_path) else {
+        return content.to_string();
+    };
+    let mut body = content.to_string();
+    if !body.ends_with('\n') {
+        body.push('\n');
+    }
+    format!(
+        "{prefix} {}\n{}{prefix} {}\n",
+        MANAGED_BEGIN,
+        body,
+        MANAGED_END,
+        prefix = prefix
+    )
+}
+
+/// `existing` with its managed region replaced by `generated`, or `None` when
+/// it has no begin and end markers.
+pub fn replace_managed_region(
+    relative_path: &str,
+    existing: &str,
+    generated: &str,
+) -> Option<String> {
+    let begin = existing.find(MANAGED_BEGIN)?;
+    let start = existing[..begin].rfind('\n').map_or(0, |index| index + 1);
+    let end_marker = begin + existing[begin..].find(MANAGED_END)?;
+    let end = existing[end_marker..]
+        .find('\n')
+        .map_or(existing.len(), |index| end_marker + index + 1);
+    Some(format!(
+        "{}{}{}",
+        &existing[..start],
+        with_managed_markers(relative_path, generated),
+        &existing[end..]
+    ))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpgradeStatus {
+    Created,
+    Updated,
+    UpToDate,
+    /// The change was shown and declined
+    Skipped,
+}
+
+/// Unified diff from `existing` to `generated` for `relative_path`, or `None`
+/// when they are the same. A missing file diffs against an empty one.
+pub fn config_diff(relative_path: &str, existing: &str, generated: &str) -> Option<String> {
+    if existing == generated {
+        return None;
+    }
+    let diff = TextDiff::from_lines(existing, generated)
+        .unified_diff()
+        .header(
+            &format!("a/{}", relative_path),
+            &format!("b/{}", relative_path),
+        )
+        .to_string();
+    Some(diff)
+}
+
+/// Rewrites stale config files in an existing project with the current
+/// templates. Changed files are backed up to `<file>.bak` first.
+/// Files generated with `--managed-markers` keep edits outside the markers.
+pub fn upgrade_project(project_path: &Path) -> Result<Vec<(&'static str, UpgradeStatus)>> {
+    upgrade_project_with(project_path, |_, _| Ok(true))
+}
+
+/// [`upgrade_project`], asking `approve` with the path and its
+/// [`config_diff`] before each file is created or rewritten.
+pub fn upgrade_project_with(
+    project_path: &Path,
+    mut approve: impl FnMut(&str, &str) -> Result<bool>,
+) -> Result<Vec<(&'static str, UpgradeStatus)>> {
+    if !project_path.join("package.json").is_file() {
+        anyhow::bail!(
+            "'{}' does not look like a project (no package.json found)",
+            project_path.display()
+        );
+    }
+
+    say!(
+        "\n🔄 Upgrading config files in: {}",
+        project_path.display().to_string().yellow()
+    );
+
+    let name = project_path
+        .canonicalize()?
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut config = ProjectConfig::new(name, false, false, false);
+    // Keep the static export a desktop shell depends on
+    if project_path.join("src-tauri/tauri.conf.json").is_file() {
+        config.desktop = Some(Desktop::Tauri);
+    }
+    // ...and the standalone output the Dockerfile copies from
+    if project_path.join("Dockerfile").is_file() {
+        config.deploy_target = DeployTarget::Docker;
+    }
+    // ...and the external packages pino's transports need
+    let logger = fs::read_to_string(project_path.join("src/libs/logger.ts")).unwrap_or_default();
+    if logger.contains("from 'pino'") {
+        config.logger = Logger::Pino;
+    }
+    // ...and the nesting plugin, when it's installed
+    let package = fs::read_to_string(project_path.join("package.json")).unwrap_or_default();
+    if package.contains("\"postcss-nesting\"") {
+        config.postcss_nesting = true;
+    }
+    // ...and the import resolver settings, when the resolver is installed
+    if package.contains("\"eslint-import-resolver-typescript\"") {
+        config.import_rules = true;
+    }
+    // ...and Biome in place of ESLint
+    if project_path.join("biome.json").is_file() {
+        config.formatter = Formatter::Biome;
+    }
+    // ...and the ignored dist/ of a publishable package
+    if package.contains("\"private\": false") {
+        config.publishable = true;
+    }
+    // ...and the Sentry wrapper around next.config.ts
+    if package.contains("\"@sentry/nextjs\"") {
+        config.observability = Observability::Sentry;
+    }
+
+    // ...and --no-incremental, which leaves *.tsbuildinfo out of .gitignore
+    let gitignore = fs::read_to_string(project_path.join(".gitignore")).unwrap_or_default();
+    if !gitignore.is_empty() && !gitignore.contains("*.tsbuildinfo") {
+        config.incremental = false;
+    }
+
+    // ...and the shared base tsconfig.json extends, with the options kept
+    // alongside it when --tsconfig-keep-options was set
+    let tsconfig = fs::read_to_string(project_path.join("tsconfig.json")).unwrap_or_default();
+    if let Ok(tsconfig) = serde_json::from_str::<serde_json::Value>(&tsconfig) {
+        if let Some(base) = tsconfig["extends"].as_str() {
+            config.tsconfig_extends = Some(base.to_string());
+            config.tsconfig_keep_options =
+                TSCONFIG_COMPILER_OPTIONS.iter().any(|(key, _, from_base)| {
+                    *from_base && tsconfig["compilerOptions"].get(key).is_some()
+                });
+        }
+    }
+
+    let mut results = Vec::new();
+
+    for (relative_path, render) in UPGRADE_FILES {
+        // Biome projects deliberately have no ESLint config
+        if *relative_path == ".eslintrc.json" && config.formatter == Formatter::Biome {
+            continue;
+        }
+        let target = project_path.join(relative_path);
+        let existing = fs::read_to_string(&target).ok();
+
+        // Files with managed markers only have their marked region replaced
+        let rendered = render(&config);
+        let content = existing
+            .as_deref()
+            .and_then(|existing| replace_managed_region(relative_path, existing, &rendered))
+            .unwrap_or(rendered);
+        let diff = config_diff(
+            relative_path,
+            existing.as_deref().unwrap_or_default(),
+            &content,
+        );
+
+        let status = match (existing, diff) {
+            (Some(_), None) => UpgradeStatus::UpToDate,
+            (_, Some(diff)) if !approve(relative_path, &diff)? => UpgradeStatus::Skipped,
+            (Some(_), _) => {
+                let backup = project_path.join(format!("{}.bak", relative_path));
+                fs::copy(&target, &backup)?;
+                atomic_write(&target, content.as_bytes())?;
+                UpgradeStatus::Updated
+            }
+            (None, _) => {
+                atomic_write(&target, content.as_bytes())?;
+                UpgradeStatus::Created
+            }
+        };
+
+        match status {
+            UpgradeStatus::Created => println!("   Created: {}", relative_path.green()),
+            UpgradeStatus::Updated => println!(
+                "   Updated: {} (backup: {}.bak)",
+                relative_path.green(),
+                relative_path
+            ),
+            UpgradeStatus::UpToDate => println!("   Up to date: {}", relative_path),
+            UpgradeStatus::Skipped => println!("   Skipped: {}", relative_path.yellow()),
+        }
+
+        results.push((*relative_path, status));
+    }
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_upgrade_project_requires_package_json() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = upgrade_project(temp_dir.path());
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("package.json"));
+    }
+
+    #[test]
+    fn test_upgrade_project_leaves_current_files_alone() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package.json"), "{}").unwrap();
+        let config = ProjectConfig::new("app".to_string(), false, false, false);
+        fs::write(
+            temp_dir.path().join("tsconfig.json"),
+            tsconfig_content(&config),
+        )
+        .unwrap();
+
+        let results = upgrade_project(temp_dir.path()).unwrap();
+        assert!(results.contains(&("tsconfig.json", UpgradeStatus::UpToDate)));
+        assert!(results.contains(&(".gitignore", UpgradeStatus::Created)));
+        assert!(!temp_dir.path().join("tsconfig.json.bak").exists());
+    }
+
+    #[test]
+    fn test_upgrade_project_keeps_tsconfig_extends() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package.json"), "{}").unwrap();
+        let mut config = ProjectConfig::new("app".to_string(), false, false, false);
+        config.tsconfig_extends = Some("../../tsconfig.base.json".to_string());
+        let current = tsconfig_content(&config);
+        let stale = current.replace("\"allowJs\": true", "\"allowJs\": false");
+        fs::write(temp_dir.path().join("tsconfig.json"), stale).unwrap();
+
+        let results = upgrade_project(temp_dir.path()).unwrap();
+        assert!(results.contains(&("tsconfig.json", UpgradeStatus::Updated)));
+        let upgraded = fs::read_to_string(temp_dir.path().join("tsconfig.json")).unwrap();
+        assert_eq!(upgraded, current);
+        assert!(upgraded.contains("\"extends\": \"../../tsconfig.base.json\""));
+
+        config.tsconfig_keep_options = true;
+        let kept = tsconfig_content(&config);
+        fs::write(temp_dir.path().join("tsconfig.json"), &kept).unwrap();
+        let results = upgrade_project(temp_dir.path()).unwrap();
+        assert!(results.contains(&("tsconfig.json", UpgradeStatus::UpToDate)));
+    }
+
+    #[test]
+    fn test_config_diff_only_for_changed_files() {
+        let config = ProjectConfig::new("app".to_string(), false, false, false);
+        let current = tsconfig_content(&config);
+        let stale = current.replace("\"strict\": true", "\"strict\": false");
+
+        let diff = config_diff("tsconfig.json", &stale, &current).unwrap();
+        assert!(diff.starts_with("--- a/tsconfig.json\n+++ b/tsconfig.json\n@@"));
+        assert!(diff.contains("\n-    \"strict\": false,\n+    \"strict\": true,\n"));
+
+        assert_eq!(config_diff("tsconfig.json", &current, &current), None);
+    }
+
+    #[test]
+    fn test_upgrade_project_with_skips_declined_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package.json"), "{}").unwrap();
+        fs::write(temp_dir.path().join("tsconfig.json"), "{}").unwrap();
+
+        let mut reviewed = Vec::new();
+        let results = upgrade_project_with(temp_dir.path(), |path, diff| {
+            reviewed.push(path.to_string());
+            assert!(!diff.is_empty());
+            Ok(path != "tsconfig.json")
+        })
+        .unwrap();
+
+        assert!(results.contains(&("tsconfig.json", UpgradeStatus::Skipped)));
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("tsconfig.json")).unwrap(),
+            "{}"
+        );
+        assert!(!temp_dir.path().join("tsconfig.json.bak").exists());
+        assert_eq!(reviewed.len(), UPGRADE_FILES.len());
+    }
+
+    #[test]
+    fn test_upgrade_project_replaces_only_the_managed_region() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package.json"), "{}").unwrap();
+        let stale = with_managed_markers(".gitignore", "/node_modules\n");
+        fs::write(
+            temp_dir.path().join(".gitignore"),
+            format!("# Team rules\n/tmp\n{}\n# Local\n*.sqlite\n", stale),
+        )
+        .unwrap();
+
+        upgrade_project(temp_dir.path()).unwrap();
+
+        let upgraded = fs::read_to_string(temp_dir.path().join(".gitignore")).unwrap();
+        assert!(upgraded.starts_with("# Team rules\n/tmp\n# aui:managed begin\n# Dependencies\n"));
+        assert!(upgraded.ends_with("# aui:managed end\n\n# Local\n*.sqlite\n"));
+        assert!(upgraded.contains(".env*.local"));
+        assert_eq!(upgraded.matches(MANAGED_BEGIN).count(), 1);
+
+        assert_eq!(
+            replace_managed_region(".gitignore", "/tmp\n", "/dist\n"),
+            None
+        );
+        assert_eq!(with_managed_markers("tsconfig.json", "{}"), "{}");
+    }
+}
@@ -0,0 +1,187 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::ProjectConfig;
+use crate::files::json_string;
+
+fn value_name<T: ValueEnum>(value: &T) -> String {
+    value
+        .to_possible_value()
+        .map(|possible| possible.get_name().to_string())
+        .unwrap_or_default()
+}
+
+/// One JSON line describing which options `config` enables. The project name,
+/// paths and other free-form values are deliberately left out.
+pub fn usage_record(config: &ProjectConfig, timestamp: u64) -> String {
+    let flags = [
+        ("install_deps", config.install_deps),
+        ("turbo", config.use_turbo),
+        ("react_query", config.use_react_query),
+        ("git", config.init_git),
+        ("git_branch", config.git_branch.is_some()),
+        ("granular_commits", config.granular_commits),
+        ("demo_asset", config.demo_asset),
+        ("husky", config.use_husky),
+        ("commitlint", config.use_commitlint),
+        ("custom_port", config.port.is_some()),
+        ("package_version", config.package_version.is_some()),
+        ("publishable", config.publishable),
+        ("theme_color", config.theme_color.is_some()),
+        ("open", config.open_dev_server),
+        ("og_route", config.use_og_route),
+        ("with_upload_example", config.with_upload_example),
+        ("server_actions", config.server_actions),
+        ("api_proxy", config.api_proxy.is_some()),
+        ("tsconfig_extends", config.tsconfig_extends.is_some()),
+        ("testing", config.use_testing),
+        ("scaffold_tests", config.scaffold_tests),
+        ("with_skeletons", config.with_skeletons),
+        ("app_states", config.app_states),
+        ("not_found", config.not_found),
+        ("common_hooks", config.common_hooks),
+        ("postcss_nesting", config.postcss_nesting),
+        ("prisma", config.use_prisma),
+        ("desktop", config.desktop.is_some()),
+        ("components_barrel", config.components_barrel),
+        ("components", !config.components.is_empty()),
+        ("ignore_scripts", config.ignore_scripts),
+        ("frozen_lockfile", config.frozen_lockfile),
+        ("ratelimit", config.use_ratelimit),
+        ("seo", config.use_seo),
+        ("keep_going", config.keep_going),
+        ("trace", config.trace),
+        ("additive", config.additive),
+        ("force", config.force),
+        ("vscode", config.vscode),
+        ("devcontainer", config.devcontainer),
+        ("import_rules", config.import_rules),
+        ("manifest", config.manifest),
+        ("dependabot", config.dependabot),
+        ("ci", config.ci),
+        ("codeowners", config.codeowners),
+        ("watchman", config.watchman),
+        ("babel", config.use_babel),
+        ("fonts_local", config.fonts_local),
+        ("title_case", config.title_case),
+        ("openapi", config.openapi.is_some()),
+        ("assets_from", config.assets_from.is_some()),
+        ("skip_directories", !config.skip_directories.is_empty()),
+        ("postgen", config.postgen.is_some()),
+        ("middleware_chain", config.middleware_chain),
+        ("strict_install", config.strict_install),
+        ("install_timeout", config.install_timeout.is_some()),
+        ("verify_lint", config.verify_lint),
+        ("audit", config.audit),
+        ("query_stale_time", config.query_stale_time.is_some()),
+        ("api_timeout", config.api_timeout.is_some()),
+        ("query_retry", config.query_retry.is_some()),
+        ("query_devtools", config.query_devtools),
+        ("strict_peers", config.strict_peers),
+        ("sort_deps", config.sort_deps),
+        ("managed_markers", config.managed_markers),
+        ("auto_install_peers", config.auto_install_peers),
+        ("incremental", config.incremental),
+        ("emoji", config.emoji),
+    ];
+    let choices = [
+        ("readme", value_name(&config.readme)),
+        ("env_validation", value_name(&config.env_validation)),
+        ("styling", value_name(&config.styling)),
+        ("formatter", value_name(&config.formatter)),
+        ("react_version", value_name(&config.react_version)),
+        ("css_structure", value_name(&config.css_structure)),
+        ("package_manager", value_name(&config.package_manager)),
+        ("readme_features", value_name(&config.readme_features)),
+        ("readme_lang", value_name(&config.readme_lang)),
+        ("deploy_target", value_name(&config.deploy_target)),
+        ("component_style", value_name(&config.component_style)),
+        ("logger", value_name(&config.logger)),
+        (
+            "audit_level",
+            config
+                .audit_level
+                .map_or("none".to_string(), |level| value_name(&level)),
+        ),
+        ("observability", value_name(&config.observability)),
+        ("robots", value_name(&config.robots)),
+        ("tool_versions", value_name(&config.tool_versions)),
+    ];
+
+    let features = flags
+        .iter()
+        .map(|(key, enabled)| format!("{}:{}", json_string(key), enabled))
+        .chain(
+            choices
+                .iter()
+                .map(|(key, value)| format!("{}:{}", json_string(key), json_string(value))),
+        )
+        .collect::<Vec<_>>()
+        .join(",");
+
+    format!(
+        "{{\"timestamp\":{},\"version\":{},\"features\":{{{}}}}}",
+        timestamp,
+        json_string(env!("CARGO_PKG_VERSION")),
+        features
+    )
+}
+
+/// Appends a usage record for `config` to the local log at `path`. Nothing is
+/// ever sent over the network.
+pub fn append_usage_log(path: &Path, config: &ProjectConfig) -> Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", usage_record(config, timestamp))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Styling;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_usage_record_leaves_out_project_name() {
+        let config = ProjectConfig::builder("secret-client-project").build();
+
+        let record = usage_record(&config, 0);
+        assert!(!record.contains("secret-client-project"));
+    }
+
+    #[test]
+    fn test_append_usage_log_writes_one_json_line_per_run() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = temp_dir.path().join("usage.jsonl");
+
+        let first = ProjectConfig::builder("first").husky(true).build();
+        let second = ProjectConfig::builder("second")
+            .react_query(false)
+            .styling(Styling::CssModules)
+            .build();
+        append_usage_log(&log, &first).unwrap();
+        append_usage_log(&log, &second).unwrap();
+
+        let content = fs::read_to_string(&log).unwrap();
+        let records: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 2);
+        assert!(records[0]["timestamp"].is_u64());
+        assert_eq!(records[0]["features"]["husky"], true);
+        assert_eq!(records[0]["features"]["react_query"], true);
+        assert_eq!(records[1]["features"]["react_query"], false);
+        assert_eq!(records[1]["features"]["styling"], "css-modules");
+    }
+}
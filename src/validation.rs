@@ -1,42 +1,777 @@
 use anyhow::Result;
 use colored::Colorize;
+use std::fmt;
+use std::path::{Component, Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
 
-pub fn check_node_version() -> Result<()> {
-    let output = Command::new("node").arg("--version").output()?;
+use crate::cli::Cli;
+use crate::config::{
+    ConfigFormat, DeployTarget, Formatter, Observability, PackageManager, ProjectConfig,
+    ReactVersion, ReadmeLang, ReadmeStyle, DIRECTORIES,
+};
+use crate::emoji::say;
+use crate::files::{assets_size, public_files, src_files, MAX_ASSETS_MB};
+use crate::git::{is_valid_branch_name, is_valid_remote_url};
+use crate::openapi::{is_remote_spec, load_spec};
+use crate::runner::CommandRunner;
+use crate::versions::MIN_NODE_VERSION;
 
-    if !output.status.success() {
-        anyhow::bail!("Node.js is not installed or not in PATH");
+/// A non-fatal problem, collected while resolving and generating and
+/// reported in one summary at the end.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Warning {
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+        }
     }
+}
 
-    let version_output = String::from_utf8_lossy(&output.stdout);
-    let version_str = version_output
-        .trim()
-        .strip_prefix('v')
-        .unwrap_or(&version_output.trim());
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
 
-    // Parse major and minor version
-    let parts: Vec<&str> = version_str.split('.').collect();
-    if parts.len() < 2 {
-        anyhow::bail!("Could not parse Node.js version: {}", version_str);
+/// Rejects contradictory option combinations before anything is generated.
+/// Returns warnings for flags that were given but have no effect and that
+/// only show up in `args`; see [`option_warnings`] for the ones `config`
+/// carries itself.
+pub fn validate_options(config: &ProjectConfig, args: &Cli) -> Result<Vec<Warning>> {
+    if config.name.trim().is_empty() {
+        anyhow::bail!("Project name cannot be empty");
     }
 
-    let major: u32 = parts[0]
-        .parse()
-        .map_err(|_| anyhow::anyhow!("Invalid major version"))?;
-    let minor: u32 = parts[1]
-        .parse()
-        .map_err(|_| anyhow::anyhow!("Invalid minor version"))?;
+    if config.name.len() > args.max_name_length {
+        anyhow::bail!(
+            "Project name is {} characters; the limit is {} (--max-name-length)",
+            config.name.len(),
+            args.max_name_length
+        );
+    }
+
+    if args.strict_name {
+        if let Some(problem) = slug_problem(&config.name) {
+            anyhow::bail!("{} (--strict-name)", problem);
+        }
+    }
+
+    check_config(config)?;
+
+    let mut warnings = Vec::new();
+    if args.turbo && args.skip_install {
+        warnings.push(Warning::new(
+            "--turbo is ignored with --skip-install, which skips the setup questions it answers; the dev script runs `next dev` without Turbopack",
+        ));
+    }
+    if args.robots.is_some() && !args.seo {
+        warnings.push(Warning::new(
+            "--robots only applies with --seo; ignoring it",
+        ));
+    }
+
+    Ok(warnings)
+}
+
+/// Rejects contradictory or malformed options that `config` carries itself,
+/// wherever the config came from.
+pub fn check_config(config: &ProjectConfig) -> Result<()> {
+    if config.granular_commits && !config.init_git {
+        anyhow::bail!("--granular-commits requires --git to initialize a repository");
+    }
+
+    if let Some(url) = &config.git_remote {
+        if !is_valid_remote_url(url) {
+            anyhow::bail!(
+                "--git-remote '{}' is not an https:// or ssh git URL (e.g. git@github.com:user/repo.git)",
+                url
+            );
+        }
+    }
+
+    if let Some(branch) = &config.git_branch {
+        if !is_valid_branch_name(branch) {
+            anyhow::bail!("--git-branch '{}' is not a valid git branch name", branch);
+        }
+    }
+
+    if let Some(version) = &config.package_version {
+        if !is_semver(version) {
+            anyhow::bail!(
+                "--pkg-version '{}' is not a semver version like 1.2.3",
+                version
+            );
+        }
+    }
+
+    if let Some(color) = &config.theme_color {
+        if !is_hex_color(color) {
+            anyhow::bail!("--theme-color '{}' is not a hex color like #2563eb", color);
+        }
+    }
+
+    if let Some(base) = &config.tsconfig_extends {
+        validate_tsconfig_extends(base)?;
+    }
+
+    if let Some(spec) = &config.openapi {
+        if !is_remote_spec(spec) {
+            load_spec(Path::new(spec))?;
+        }
+    }
+
+    if let Some(target) = &config.api_proxy {
+        if !is_http_url(target) {
+            anyhow::bail!(
+                "--api-proxy '{}' is not an http(s) URL like https://api.example.com",
+                target
+            );
+        }
+    }
+
+    if let Some(dir) = &config.assets_from {
+        validate_assets_dir(Path::new(dir))?;
+    }
+
+    check_skipped_directories(config)?;
+
+    if config.desktop.is_some() && config.deploy_target == DeployTarget::Docker {
+        anyhow::bail!(
+            "--deploy-target docker needs a Next.js server, but --desktop builds a static export"
+        );
+    }
+
+    if config.desktop.is_some() && config.use_og_route {
+        anyhow::bail!(
+            "--og-route needs a server, but --desktop builds a static export; drop one of them"
+        );
+    }
+
+    if config.desktop.is_some() && config.api_proxy.is_some() {
+        anyhow::bail!(
+            "--api-proxy needs a server, but --desktop builds a static export; drop one of them"
+        );
+    }
+
+    if config.desktop.is_some() && config.middleware_chain {
+        anyhow::bail!(
+            "--with-middleware-chain needs a server, but --desktop builds a static export; drop one of them"
+        );
+    }
+
+    if config.desktop.is_some() && config.with_upload_example {
+        anyhow::bail!(
+            "--with-upload-example needs a server, but --desktop builds a static export; drop one of them"
+        );
+    }
+
+    if config.desktop.is_some() && config.server_actions {
+        anyhow::bail!(
+            "--with-server-actions needs a server, but --desktop builds a static export; drop one of them"
+        );
+    }
+
+    Ok(())
+}
+
+/// Outcome of `aui-next-gen validate`: problems that make a config file
+/// unusable, and option combinations that are merely suspicious.
+#[derive(Debug, Default)]
+pub struct ConfigReport {
+    pub errors: Vec<String>,
+    pub warnings: Vec<Warning>,
+}
+
+impl ConfigReport {
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// The top-level keys of a config file as a TOML table, whatever its format.
+fn config_table(content: &str, format: ConfigFormat) -> std::result::Result<toml::Table, String> {
+    let value: serde_json::Value = match format {
+        ConfigFormat::Toml => {
+            return toml::from_str(content).map_err(|err| {
+                let line = err
+                    .span()
+                    .map(|span| content[..span.start].matches('\n').count() + 1)
+                    .unwrap_or(1);
+                format!("not valid TOML (line {}): {}", line, err.message())
+            });
+        }
+        ConfigFormat::Json => {
+            serde_json::from_str(content).map_err(|err| format!("not valid JSON: {}", err))?
+        }
+        ConfigFormat::Yaml => {
+            serde_yaml_ng::from_str(content).map_err(|err| format!("not valid YAML: {}", err))?
+        }
+    };
+
+    let serde_json::Value::Object(map) = value else {
+        return Err(format!(
+            "not a {} mapping of option names to values",
+            format.name()
+        ));
+    };
+    // TOML has no null; an unset option is a missing key
+    let map: serde_json::Map<String, serde_json::Value> = map
+        .into_iter()
+        .filter(|(_, value)| !value.is_null())
+        .collect();
+    match toml::Value::try_from(map) {
+        Ok(toml::Value::Table(table)) => Ok(table),
+        Ok(_) => unreachable!("a JSON object serializes to a table"),
+        Err(err) => Err(err.to_string()),
+    }
+}
+
+/// Checks a config file in the `--print-config` format without generating
+/// anything: every key must be a known option with a valid value, `name`
+/// must stay inside the current directory, and the options must not
+/// contradict each other.
+pub fn validate_config(content: &str, format: ConfigFormat) -> ConfigReport {
+    let mut report = ConfigReport::default();
+    let table = match config_table(content, format) {
+        Ok(table) => table,
+        Err(err) => {
+            report.errors.push(err);
+            return report;
+        }
+    };
+
+    // One key at a time, so every bad entry is reported rather than the first
+    let mut known = toml::Table::new();
+    for (key, value) in table {
+        let entry = toml::Table::from_iter([(key.clone(), value.clone())]);
+        match toml::Value::Table(entry).try_into::<ProjectConfig>() {
+            Ok(_) => {
+                known.insert(key, value);
+            }
+            Err(err) if err.message().starts_with("unknown field") => {
+                report.errors.push(format!("unknown key `{}`", key));
+            }
+            Err(err) => report.errors.push(format!("{}: {}", key, err.message())),
+        }
+    }
+
+    let config = match toml::Value::Table(known).try_into::<ProjectConfig>() {
+        Ok(config) => config,
+        Err(err) => {
+            report.errors.push(err.message().to_string());
+            return report;
+        }
+    };
+
+    let name = Path::new(&config.name);
+    if name.is_absolute() || name.components().any(|part| part == Component::ParentDir) {
+        report.errors.push(format!(
+            "name: '{}' must be a relative path inside the current directory",
+            config.name
+        ));
+    }
+    if let Err(err) = check_config(&config) {
+        report.errors.push(err.to_string());
+    }
+
+    report.warnings = option_warnings(&config);
+    report
+}
+
+/// Refuses to skip a directory that a generated file is written into.
+fn check_skipped_directories(config: &ProjectConfig) -> Result<()> {
+    let generated = [src_files(config), public_files(config)].concat();
+    for dir in &config.skip_directories {
+        let prefix = format!("{}/", dir);
+        if let Some(file) = generated.iter().find(|file| file.starts_with(&prefix)) {
+            anyhow::bail!(
+                "--skip-directory {} can't be skipped: the project writes {} there",
+                dir,
+                file
+            );
+        }
+        if dir == "public" && config.assets_from.is_some() {
+            anyhow::bail!("--skip-directory public can't be skipped: --assets-from copies into it");
+        }
+    }
+    Ok(())
+}
+
+/// Warnings for option combinations in `config` where one of the options has
+/// no effect or part of a feature is skipped.
+pub fn option_warnings(config: &ProjectConfig) -> Vec<Warning> {
+    let mut warnings = Vec::new();
+
+    if config.git_branch.is_some() && !config.init_git {
+        warnings.push(Warning::new(
+            "--git-branch needs --git to initialize a repository; ignoring it",
+        ));
+    }
+
+    for dir in &config.skip_directories {
+        if !DIRECTORIES.contains(&dir.as_str()) {
+            warnings.push(Warning::new(format!(
+                "--skip-directory {} is not a scaffold directory ({}); ignoring it",
+                dir,
+                DIRECTORIES.join(", ")
+            )));
+        }
+    }
+
+    if config.publishable {
+        warnings.push(Warning::new(
+            "--publishable is meant for component library scaffolds; publishing a Next.js app to npm is unusual",
+        ));
+    }
+
+    if config.import_rules && config.formatter == Formatter::Biome {
+        warnings.push(Warning::new(
+            "--import-rules configures ESLint, which --formatter biome replaces; ignoring it",
+        ));
+    }
+
+    if config.frozen_lockfile && !config.install_deps {
+        warnings.push(Warning::new(
+            "--frozen-lockfile has no effect with --skip-install",
+        ));
+    }
+
+    if config.manifest && !config.demo_asset {
+        warnings.push(Warning::new(
+            "--manifest references /icon-192.png and /icon-512.png, which aren't generated; add them to public/ (--demo-asset adds an SVG icon instead)",
+        ));
+    }
+
+    if config.react_version == ReactVersion::V18 {
+        warnings.push(Warning::new(
+            "--react-version 18 is unusual with Next.js 15: its App Router expects React 19, and React 18 is only supported by the Pages Router",
+        ));
+    }
+
+    if config.codeowners && config.git_remote.is_none() {
+        warnings.push(Warning::new(
+            "--codeowners has no --git-remote to take the owner from; fill it in in .github/CODEOWNERS",
+        ));
+    }
+
+    if config.git_remote.is_some() && !config.init_git {
+        warnings.push(Warning::new(
+            "--git-remote needs --git to initialize a repository; skipping the remote",
+        ));
+    }
+
+    if config.tsconfig_extends.is_none() && config.tsconfig_keep_options {
+        warnings.push(Warning::new(
+            "--tsconfig-keep-options has no effect without --tsconfig-extends",
+        ));
+    }
+
+    let peer_flags_used = config.strict_peers || !config.auto_install_peers;
+    if peer_flags_used
+        && matches!(
+            config.package_manager,
+            PackageManager::Yarn | PackageManager::Bun
+        )
+    {
+        warnings.push(Warning::new(format!(
+            "--strict-peers and --no-auto-install-peers only apply to pnpm and npm; no .npmrc is generated for {}",
+            config.package_manager.command()
+        )));
+    }
+
+    if config.observability != Observability::None && config.desktop.is_some() {
+        warnings.push(Warning::new(
+            "--observability hooks run on a Next.js server, but --desktop builds a static export; only Sentry's browser reporting will work",
+        ));
+    }
+
+    if config.use_commitlint && !config.use_husky {
+        warnings.push(Warning::new(
+            "--commitlint needs --husky for its commit-msg hook; skipping commitlint",
+        ));
+    }
+
+    if config.open_dev_server && !config.install_deps {
+        warnings.push(Warning::new(
+            "--open needs installed dependencies; the dev server will not be started",
+        ));
+    }
+
+    if !config.use_react_query
+        && (config.query_stale_time.is_some() || config.query_retry.is_some())
+    {
+        warnings.push(Warning::new(
+            "--query-stale-time and --query-retry only apply with React Query; ignoring them",
+        ));
+    }
+
+    if !config.use_react_query && config.api_timeout.is_some() {
+        warnings.push(Warning::new(
+            "--api-timeout sets the React Query API client's timeout; ignoring it without React Query",
+        ));
+    }
+
+    if !config.use_react_query && !config.query_devtools {
+        warnings.push(Warning::new(
+            "--no-query-devtools only applies with React Query; ignoring it",
+        ));
+    }
+
+    if config.openapi.is_some() && !config.use_react_query {
+        warnings.push(Warning::new(
+            "--openapi types are only wired into the React Query API client; src/libs/api.ts is not generated without it",
+        ));
+    }
+
+    if config.use_babel {
+        warnings.push(Warning::new(
+            "--use-babel replaces SWC with Babel; expect noticeably slower builds and dev reloads",
+        ));
+    }
+
+    if config.fonts_local && config.use_babel {
+        warnings.push(Warning::new(
+            "--fonts-local has no effect with --use-babel; the layout doesn't load a font without SWC",
+        ));
+    }
+
+    // Turbopack has no Babel or webpack loader pipeline, so the two options
+    // disagree about who compiles the app in development
+    if config.use_babel && config.use_turbo {
+        warnings.push(Warning::new(
+            "--turbo is incompatible with --use-babel: Turbopack ignores .babelrc, so `dev` compiles with SWC while `build` uses Babel",
+        ));
+    }
+
+    if config.verify_lint && !config.install_deps {
+        warnings.push(Warning::new(
+            "--verify-lint needs installed dependencies; skipped the lint and type checks",
+        ));
+    }
+
+    if config.scaffold_tests && !config.use_testing {
+        warnings.push(Warning::new(
+            "--scaffold-tests needs --testing for Vitest; skipping the component tests",
+        ));
+    } else if config.scaffold_tests && config.components.is_empty() {
+        warnings.push(Warning::new(
+            "--scaffold-tests has no effect without --components",
+        ));
+    }
+
+    if config.audit && !config.install_deps {
+        warnings.push(Warning::new(
+            "--audit needs installed dependencies; skipped the vulnerability audit",
+        ));
+    } else if config.audit && config.package_manager == PackageManager::Bun {
+        warnings.push(Warning::new(format!(
+            "--audit isn't supported for bun {}; skipped the vulnerability audit",
+            crate::versions::BUN_VERSION
+        )));
+    }
+
+    if config.readme_lang != ReadmeLang::En && config.readme == ReadmeStyle::None {
+        warnings.push(Warning::new(
+            "--readme-lang has no effect with --readme none",
+        ));
+    }
+
+    if config.install_timeout.is_some() && !config.install_deps {
+        warnings.push(Warning::new(
+            "--install-timeout has no effect with --skip-install",
+        ));
+    }
+
+    if config.ignore_scripts && !config.install_deps {
+        warnings.push(Warning::new(
+            "--ignore-scripts has no effect with --skip-install",
+        ));
+    }
+
+    warnings
+}
+
+/// Output paths nested deeper than this are almost certainly a mistake.
+pub const MAX_OUTPUT_DEPTH: usize = 32;
+
+/// `path` made absolute against the current directory, with `.` and `..`
+/// resolved lexically.
+fn normalize_path(path: &Path) -> Result<PathBuf> {
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    Ok(normalized)
+}
+
+/// Refuses output paths that are likely accidents, e.g. `$EMPTY/app`
+/// expanding to `/app`: a filesystem root, a directory directly under it,
+/// the home directory itself, or something absurdly deep. `--force` skips
+/// the check.
+pub fn check_output_path(path: &Path, force: bool) -> Result<()> {
+    if force {
+        return Ok(());
+    }
+
+    let target = normalize_path(path)?;
+    let hint = "pass --force if this is intended";
+
+    match target.parent() {
+        None => anyhow::bail!(
+            "Refusing to generate into the filesystem root '{}'; {}",
+            target.display(),
+            hint
+        ),
+        Some(parent) if parent.parent().is_none() => anyhow::bail!(
+            "Refusing to generate into top-level directory '{}'; {}",
+            target.display(),
+            hint
+        ),
+        Some(_) => {}
+    }
+
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    if let Some(home) = home {
+        if normalize_path(Path::new(&home))? == target {
+            anyhow::bail!(
+                "Refusing to generate into your home directory '{}'; {}",
+                target.display(),
+                hint
+            );
+        }
+    }
+
+    let depth = target.components().count();
+    if depth > MAX_OUTPUT_DEPTH {
+        anyhow::bail!(
+            "Refusing to generate {} levels deep in '{}'; {}",
+            depth,
+            target.display(),
+            hint
+        );
+    }
+
+    Ok(())
+}
+
+/// Longest path the generator writes inside a project. Kept in sync by
+/// `test_longest_generated_path_covers_every_file`.
+pub const LONGEST_GENERATED_PATH: &str = "src/components/ui/Button.module.css";
+
+/// Longest file or directory name most filesystems accept, in bytes.
+pub const MAX_NAME_BYTES: usize = 255;
+
+/// Longest path the platform accepts, in bytes.
+#[cfg(target_os = "linux")]
+pub const MAX_PATH_BYTES: usize = 4096;
+#[cfg(windows)]
+pub const MAX_PATH_BYTES: usize = 260;
+#[cfg(not(any(target_os = "linux", windows)))]
+pub const MAX_PATH_BYTES: usize = 1024;
+
+/// npm rejects package names longer than this.
+pub const NPM_NAME_MAX_LENGTH: usize = 214;
+
+/// Pre-flight check that every file generated under `project_path` fits the
+/// platform's name and path length limits, so an overly long name fails
+/// before anything is written instead of halfway through.
+pub fn check_path_limits(project_path: &Path) -> Result<()> {
+    let target = normalize_path(project_path)?;
+
+    if let Some(name) = target.file_name() {
+        let bytes = name.to_string_lossy().len();
+        if bytes > MAX_NAME_BYTES {
+            anyhow::bail!(
+                "Project directory name is {} bytes; filesystems allow at most {}",
+                bytes,
+                MAX_NAME_BYTES
+            );
+        }
+    }
+
+    let longest = target.join(LONGEST_GENERATED_PATH);
+    let bytes = longest.to_string_lossy().len();
+    if bytes > MAX_PATH_BYTES {
+        anyhow::bail!(
+            "Generated files would have paths of {} bytes (e.g. {}), over the platform limit of {}; choose a shorter name or output directory",
+            bytes,
+            LONGEST_GENERATED_PATH,
+            MAX_PATH_BYTES
+        );
+    }
+
+    Ok(())
+}
+
+/// `name` lowercased, with each run of characters other than letters,
+/// digits, `.`, `_` and `-` turned into a single `-`.
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.trim().chars().flat_map(char::to_lowercase) {
+        if c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-') {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// Why `name` isn't usable as-is under `--strict-name`: it must already be
+/// its own slug.
+pub fn slug_problem(name: &str) -> Option<String> {
+    let slug = slugify(name);
+    if slug == name {
+        return None;
+    }
+    Some(if slug.is_empty() {
+        format!("Project name '{}' has no usable characters", name)
+    } else {
+        format!(
+            "Project name '{}' is not a valid slug; use '{}' instead",
+            name, slug
+        )
+    })
+}
+
+/// `MAJOR.MINOR.PATCH` with optional `-prerelease` and `+build` parts, as
+/// npm's semver parses them.
+pub fn is_semver(version: &str) -> bool {
+    let (version, build) = match version.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (version, None),
+    };
+    let (core, prerelease) = match version.split_once('-') {
+        Some((core, prerelease)) => (core, Some(prerelease)),
+        None => (version, None),
+    };
+
+    let is_number = |part: &str| {
+        !part.is_empty()
+            && part.chars().all(|c| c.is_ascii_digit())
+            && (part == "0" || !part.starts_with('0'))
+    };
+    let is_identifier = |part: &str| {
+        !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+    };
+
+    let numbers: Vec<&str> = core.split('.').collect();
+    numbers.len() == 3
+        && numbers.iter().all(|part| is_number(part))
+        && prerelease.is_none_or(|prerelease| {
+            prerelease.split('.').all(|part| {
+                is_identifier(part)
+                    && (!part.chars().all(|c| c.is_ascii_digit()) || is_number(part))
+            })
+        })
+        && build.is_none_or(|build| build.split('.').all(is_identifier))
+}
+
+/// An `http://` or `https://` URL with a host, e.g. `http://localhost:8000`.
+pub fn is_http_url(url: &str) -> bool {
+    let Some(rest) = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+    else {
+        return false;
+    };
+    let host = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    !host.is_empty() && !url.chars().any(char::is_whitespace)
+}
+
+/// `#rgb` or `#rrggbb`, the forms browsers accept for `theme-color`.
+pub fn is_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+/// Checks `dir` is a directory whose files fit in `MAX_ASSETS_MB`, so a
+/// mistyped `--assets-from ~` doesn't copy a home directory into public/.
+pub fn validate_assets_dir(dir: &Path) -> Result<()> {
+    if !dir.is_dir() {
+        anyhow::bail!("--assets-from '{}' is not a directory", dir.display());
+    }
+
+    let size = assets_size(dir)?;
+    if size > MAX_ASSETS_MB * 1024 * 1024 {
+        anyhow::bail!(
+            "--assets-from '{}' holds {:.1} MB of files, more than the {} MB limit",
+            dir.display(),
+            size as f64 / 1024.0 / 1024.0,
+            MAX_ASSETS_MB
+        );
+    }
+    Ok(())
+}
 
-    // Next.js 15 requires Node.js 18.18.0+
-    let required_major = 18;
-    let required_minor = 18;
+/// Checks that `base` is something TypeScript can resolve in `extends`: a
+/// relative or absolute path to a `.json` file, or a package specifier.
+pub fn validate_tsconfig_extends(base: &str) -> Result<()> {
+    if base.trim().is_empty() {
+        anyhow::bail!("--tsconfig-extends cannot be empty");
+    }
+    if base.chars().any(char::is_whitespace) {
+        anyhow::bail!("--tsconfig-extends '{}' must not contain whitespace", base);
+    }
+    if base.contains("://") {
+        anyhow::bail!(
+            "--tsconfig-extends '{}' looks like a URL; TypeScript only resolves file paths and packages",
+            base
+        );
+    }
+
+    let is_path = base.starts_with("./") || base.starts_with("../") || base.starts_with('/');
+    if is_path && !base.ends_with(".json") {
+        anyhow::bail!("--tsconfig-extends '{}' must point at a .json file", base);
+    }
 
-    if major > required_major || (major == required_major && minor >= required_minor) {
-        println!("✅ Node.js {} (compatible)", version_str);
+    Ok(())
+}
+
+/// The installed Node.js version without its `v` prefix, e.g. `20.11.1`.
+pub fn installed_node_version() -> Result<String> {
+    let output = Command::new("node").arg("--version").output();
+    let output = match output {
+        Ok(output) if output.status.success() => output,
+        _ => anyhow::bail!("Node.js is not installed or not in PATH"),
+    };
+
+    let version_output = String::from_utf8_lossy(&output.stdout);
+    let version = version_output.trim();
+    Ok(version.strip_prefix('v').unwrap_or(version).to_string())
+}
+
+/// Checks the installed Node.js is at least `minimum`, as `(major, minor)`.
+pub fn check_node_version(minimum: (u32, u32)) -> Result<()> {
+    let version_str = installed_node_version()?;
+    let version_str = version_str.as_str();
+
+    let (major, minor) = parse_node_version(version_str)?;
+    let (required_major, required_minor) = minimum;
+
+    if is_node_version_at_least((major, minor), minimum) {
+        say!("✅ Node.js {} (compatible)", version_str);
         Ok(())
     } else {
-        println!("{}", "❌ Node.js version is too old".red());
+        say!("{}", "❌ Node.js version is too old".red());
         println!("   Current: v{}", version_str);
         println!(
             "   Required: v{}.{}.0 or higher",
@@ -55,11 +790,11 @@ pub fn check_and_install_pnpm() -> Result<()> {
 
     match which::which("pnpm") {
         Ok(_) => {
-            println!("✅ pnpm found");
+            say!("✅ pnpm found");
             Ok(())
         }
         Err(_) => {
-            println!("{}", "⚠️  pnpm not found".yellow());
+            say!("{}", "⚠️  pnpm not found".yellow());
 
             let install_pnpm = Confirm::new()
                 .with_prompt("Install pnpm globally?")
@@ -69,7 +804,7 @@ pub fn check_and_install_pnpm() -> Result<()> {
             if install_pnpm {
                 install_pnpm_global()?;
             } else {
-                println!(
+                say!(
                     "{}",
                     "❌ pnpm is required. Please install it manually:".red()
                 );
@@ -84,17 +819,17 @@ pub fn check_and_install_pnpm() -> Result<()> {
 }
 
 fn install_pnpm_global() -> Result<()> {
-    println!("{}", "📦 Installing pnpm globally...".blue());
+    say!("{}", "📦 Installing pnpm globally...".blue());
 
     let output = Command::new("npm")
-        .args(&["install", "-g", "pnpm"])
+        .args(["install", "-g", "pnpm"])
         .output()?;
 
     if output.status.success() {
-        println!("{}", "✅ pnpm installed successfully!".green());
+        say!("{}", "✅ pnpm installed successfully!".green());
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
-        println!("{}", "❌ Failed to install pnpm".red());
+        say!("{}", "❌ Failed to install pnpm".red());
         println!("Error: {}", error);
         anyhow::bail!("pnpm installation failed");
     }
@@ -102,19 +837,78 @@ fn install_pnpm_global() -> Result<()> {
     Ok(())
 }
 
-pub fn install_dependencies_with_pnpm(project_path: &std::path::Path) -> Result<()> {
-    let output = Command::new("pnpm")
-        .arg("install")
-        .current_dir(project_path)
-        .output()?;
+/// Checks a package manager other than pnpm is on PATH. pnpm is handled by
+/// [`check_and_install_pnpm`], which can also install it.
+pub fn check_package_manager(package_manager: PackageManager) -> Result<()> {
+    let command = package_manager.command();
+    if which::which(command).is_err() {
+        anyhow::bail!("{} is not installed or not in PATH", command);
+    }
+
+    say!("✅ {} found", command);
+    Ok(())
+}
+
+/// Warns when `--frozen-lockfile` can't take effect because `project_path`
+/// has no lockfile yet, which is always the case for a new project.
+pub fn frozen_lockfile_warning(
+    config: &ProjectConfig,
+    project_path: &std::path::Path,
+) -> Option<Warning> {
+    let lockfile = config.package_manager.lockfile();
+    if !config.frozen_lockfile || !config.install_deps || project_path.join(lockfile).is_file() {
+        return None;
+    }
+    Some(Warning::new(format!(
+        "--frozen-lockfile needs an existing {}; {} has none, so dependencies are installed normally",
+        lockfile,
+        project_path.display()
+    )))
+}
+
+/// Arguments for installing in `project_path`. `--frozen-lockfile` only
+/// takes effect when the package manager's lockfile is already there.
+pub fn install_args(config: &ProjectConfig, project_path: &std::path::Path) -> Vec<&'static str> {
+    let package_manager = config.package_manager;
+    let frozen = config.frozen_lockfile && project_path.join(package_manager.lockfile()).is_file();
+    let mut args = match (frozen, package_manager) {
+        (true, PackageManager::Npm) => vec!["ci"],
+        (true, _) => vec!["install", "--frozen-lockfile"],
+        (false, _) => vec!["install"],
+    };
+    if config.ignore_scripts {
+        args.push("--ignore-scripts");
+    }
+    args
+}
+
+pub fn install_dependencies(
+    runner: &dyn CommandRunner,
+    project_path: &std::path::Path,
+    config: &ProjectConfig,
+) -> Result<()> {
+    let command = config.package_manager.command();
+    let timeout = config.install_timeout.map(Duration::from_secs);
+    let output = runner.output(
+        command,
+        &install_args(config, project_path),
+        project_path,
+        timeout,
+    )?;
 
     if output.status.success() {
-        println!("{}", "✅ Dependencies installed successfully!".green());
+        say!("{}", "✅ Dependencies installed successfully!".green());
+    } else if config.strict_install {
+        let error = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!(
+            "Failed to install dependencies (--strict-install):\n{}",
+            error
+        );
     } else {
         let error = String::from_utf8_lossy(&output.stderr);
-        println!("{}", "❌ Failed to install dependencies".red());
+        say!("{}", "❌ Failed to install dependencies".red());
         println!("Error: {}", error);
-        println!("You can install manually with: pnpm install");
+        println!("You can install manually with: {} install", command);
     }
 
     Ok(())
@@ -141,14 +935,540 @@ pub fn parse_node_version(version_str: &str) -> Result<(u32, u32)> {
 
 // Helper function for testing - checks if version is compatible
 pub fn is_node_version_compatible(major: u32, minor: u32) -> bool {
-    let required_major = 18;
-    let required_minor = 18;
-    major > required_major || (major == required_major && minor >= required_minor)
+    is_node_version_at_least((major, minor), MIN_NODE_VERSION)
+}
+
+pub fn is_node_version_at_least(version: (u32, u32), minimum: (u32, u32)) -> bool {
+    version >= minimum
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::cli::apply_cli_options;
+    use crate::config::Component;
+    use clap::Parser;
+
+    fn resolve(argv: &[&str]) -> (ProjectConfig, Cli) {
+        let args = Cli::parse_from(argv);
+        let mut config = ProjectConfig::new(
+            args.name.clone().unwrap_or_default(),
+            !args.skip_install,
+            args.turbo && !args.skip_install,
+            false,
+        );
+        apply_cli_options(&mut config, &args);
+        (config, args)
+    }
+
+    #[test]
+    fn test_validate_options_rejects_granular_commits_without_git() {
+        let (config, args) = resolve(&["aui-next-gen", "my-app", "--granular-commits"]);
+
+        let result = validate_options(&config, &args);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("--git"));
+    }
+
+    #[test]
+    fn test_validate_options_rejects_blank_name() {
+        let (config, args) = resolve(&["aui-next-gen", "   "]);
+
+        let result = validate_options(&config, &args);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("empty"));
+    }
+
+    #[test]
+    fn test_validate_options_accepts_valid_combination() {
+        let (config, args) = resolve(&[
+            "aui-next-gen",
+            "my-app",
+            "--git",
+            "--granular-commits",
+            "--turbo",
+        ]);
+
+        assert!(validate_options(&config, &args).is_ok());
+    }
+
+    #[test]
+    fn test_validate_options_warns_but_accepts_noop_combination() {
+        let (config, args) = resolve(&["aui-next-gen", "my-app", "--turbo", "--skip-install"]);
+
+        let warnings = validate_options(&config, &args).unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.starts_with("--turbo"));
+    }
+
+    #[test]
+    fn test_option_warnings_lists_each_noop_flag() {
+        let (config, _) = resolve(&[
+            "aui-next-gen",
+            "my-app",
+            "--skip-install",
+            "--commitlint",
+            "--ignore-scripts",
+        ]);
+
+        let warnings = option_warnings(&config);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].message.starts_with("--commitlint"));
+        assert!(warnings[1].message.starts_with("--ignore-scripts"));
+    }
+
+    #[test]
+    fn test_option_warnings_flags_turbo_with_babel() {
+        let (config, _) = resolve(&["aui-next-gen", "my-app", "--turbo", "--use-babel"]);
+
+        let warnings = option_warnings(&config);
+        assert!(warnings.iter().any(|warning| warning
+            .message
+            .starts_with("--turbo is incompatible with --use-babel")));
+
+        let (config, _) = resolve(&["aui-next-gen", "my-app", "--turbo"]);
+        assert!(option_warnings(&config).is_empty());
+    }
+
+    #[test]
+    fn test_validate_config_accepts_printed_config() {
+        let config = ProjectConfig::builder("my-app")
+            .git(true)
+            .seo(true)
+            .logger(crate::config::Logger::Pino)
+            .build();
+
+        for format in [ConfigFormat::Toml, ConfigFormat::Json, ConfigFormat::Yaml] {
+            let report = validate_config(&config.to_format(format).unwrap(), format);
+            assert!(report.is_valid(), "{}: {:?}", format.name(), report.errors);
+            assert!(report.warnings.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_validate_config_flags_unknown_keys_bad_values_and_traversal() {
+        let report = validate_config(
+            "name = \"../../etc/app\"\ncolour = true\nstyling = \"sass\"\nuse_seo = true\n",
+            ConfigFormat::Toml,
+        );
+
+        assert_eq!(report.errors.len(), 3, "{:?}", report.errors);
+        assert_eq!(report.errors[0], "unknown key `colour`");
+        assert!(report.errors[1].starts_with("styling: unknown variant `sass`"));
+        assert_eq!(
+            report.errors[2],
+            "name: '../../etc/app' must be a relative path inside the current directory"
+        );
+    }
+
+    #[test]
+    fn test_validate_config_reads_yaml_and_json() {
+        let report = validate_config("name: yaml-app\ncolour: true\n", ConfigFormat::Yaml);
+        assert_eq!(report.errors, ["unknown key `colour`"]);
+
+        let report = validate_config("[\"name\"]", ConfigFormat::Json);
+        assert_eq!(
+            report.errors,
+            ["not a JSON mapping of option names to values"]
+        );
+    }
+
+    #[test]
+    fn test_validate_options_rejects_malformed_git_remote() {
+        let (config, args) =
+            resolve(&["aui-next-gen", "my-app", "--git", "--git-remote", "origin"]);
+
+        let result = validate_options(&config, &args);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("--git-remote"));
+    }
+
+    #[test]
+    fn test_validate_options_rejects_invalid_git_branch() {
+        let (config, args) =
+            resolve(&["aui-next-gen", "my-app", "--git", "--git-branch", "feat..x"]);
+
+        let err = validate_options(&config, &args).unwrap_err();
+        assert!(err.to_string().contains("--git-branch 'feat..x'"));
+    }
+
+    #[test]
+    fn test_react_version_18_installs_react_18_and_warns() {
+        let (config, args) = resolve(&["aui-next-gen", "my-app", "--react-version", "18"]);
+
+        assert!(validate_options(&config, &args).is_ok());
+        assert!(option_warnings(&config)
+            .iter()
+            .any(|warning| warning.to_string().contains("--react-version 18")));
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        crate::files::write_files(
+            temp_dir.path(),
+            &crate::files::package_json_files(&config).unwrap(),
+        )
+        .unwrap();
+        let package: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(temp_dir.path().join("package.json")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(package["dependencies"]["react"], "^18.3.1");
+        assert_eq!(package["dependencies"]["react-dom"], "^18.3.1");
+        assert_eq!(package["devDependencies"]["@types/react"], "^18.3.0");
+
+        let (default, _) = resolve(&["aui-next-gen", "my-app"]);
+        assert_eq!(default.react_version, ReactVersion::V19);
+        assert!(option_warnings(&default).is_empty());
+    }
+
+    #[test]
+    fn test_strict_name_rejects_names_that_are_not_slugs() {
+        let (config, args) = resolve(&["aui-next-gen", "My App", "--strict-name"]);
+        let err = validate_options(&config, &args).unwrap_err();
+        assert!(err.to_string().contains("use 'my-app' instead"));
+
+        let (config, args) = resolve(&["aui-next-gen", "my-app", "--strict-name"]);
+        assert!(validate_options(&config, &args).is_ok());
+
+        let (config, args) = resolve(&["aui-next-gen", "My App"]);
+        assert!(validate_options(&config, &args).is_ok());
+
+        assert_eq!(slugify("  Shop: Admin  UI! "), "shop-admin-ui");
+        assert_eq!(slugify("api_v2.next"), "api_v2.next");
+        assert!(slug_problem("!!!")
+            .unwrap()
+            .contains("no usable characters"));
+    }
+
+    #[test]
+    fn test_validate_options_checks_the_version_is_semver() {
+        for version in ["1.2.3", "0.0.1", "2.0.0-beta.1", "1.0.0-rc.1+build.5"] {
+            let (config, args) = resolve(&["aui-next-gen", "my-app", "--pkg-version", version]);
+            assert!(
+                validate_options(&config, &args).is_ok(),
+                "{} rejected",
+                version
+            );
+        }
+
+        for version in ["1.2", "v1.2.3", "01.2.3", "1.2.3-", "1.2.3-01", "latest"] {
+            let (config, args) = resolve(&["aui-next-gen", "my-app", "--pkg-version", version]);
+            let err = validate_options(&config, &args).unwrap_err();
+            assert!(
+                err.to_string().contains("--pkg-version"),
+                "{} accepted",
+                version
+            );
+        }
+
+        // --version is left to report the generator's own version
+        let err = Cli::try_parse_from(["aui-next-gen", "--version"])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), clap::error::ErrorKind::DisplayVersion);
+    }
+
+    #[test]
+    fn test_validate_options_checks_the_api_proxy_target() {
+        for target in ["http://localhost:8000", "https://api.example.com/v1"] {
+            let (config, args) = resolve(&["aui-next-gen", "my-app", "--api-proxy", target]);
+            assert!(
+                validate_options(&config, &args).is_ok(),
+                "{} rejected",
+                target
+            );
+        }
+
+        for target in [
+            "localhost:8000",
+            "ftp://api.example.com",
+            "https://",
+            "http:// x",
+        ] {
+            let (config, args) = resolve(&["aui-next-gen", "my-app", "--api-proxy", target]);
+            let err = validate_options(&config, &args).unwrap_err();
+            assert!(
+                err.to_string().contains("--api-proxy"),
+                "{} accepted",
+                target
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_options_rejects_non_hex_theme_color() {
+        let (config, args) = resolve(&["aui-next-gen", "my-app", "--theme-color", "blue"]);
+        let err = validate_options(&config, &args).unwrap_err();
+        assert!(err.to_string().contains("--theme-color 'blue'"));
+
+        for color in ["#fff", "#2563EB"] {
+            let (config, args) = resolve(&["aui-next-gen", "my-app", "--theme-color", color]);
+            assert!(validate_options(&config, &args).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_components_accept_lists_and_repeats_without_duplicates() {
+        let (config, _) = resolve(&[
+            "aui-next-gen",
+            "my-app",
+            "--components",
+            "spinner,table",
+            "--component",
+            "spinner",
+        ]);
+
+        assert_eq!(config.components, [Component::Table, Component::Spinner]);
+    }
+
+    #[test]
+    fn test_query_options_reject_negative_values() {
+        for flag in ["--query-stale-time", "--query-retry"] {
+            let result = Cli::try_parse_from(["aui-next-gen", "my-app", flag, "-5"]);
+            assert!(result.is_err(), "{} accepted -5", flag);
+        }
+
+        let (config, _) = resolve(&[
+            "aui-next-gen",
+            "my-app",
+            "--query-stale-time",
+            "0",
+            "--query-retry",
+            "3",
+        ]);
+        assert_eq!(config.query_stale_time, Some(0));
+        assert_eq!(config.query_retry, Some(3));
+    }
+
+    #[test]
+    fn test_validate_options_rejects_missing_or_invalid_openapi_spec() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let missing = temp_dir.path().join("missing.yaml");
+        let invalid = temp_dir.path().join("invalid.json");
+        std::fs::write(&invalid, "{ \"name\": \"not a spec\" }").unwrap();
+
+        for spec in [&missing, &invalid] {
+            let spec = spec.display().to_string();
+            let (config, args) = resolve(&["aui-next-gen", "my-app", "--openapi", &spec]);
+            assert!(
+                validate_options(&config, &args).is_err(),
+                "{} accepted",
+                spec
+            );
+        }
+
+        let (config, args) = resolve(&[
+            "aui-next-gen",
+            "my-app",
+            "--openapi",
+            "https://api.example.com/openapi.json",
+        ]);
+        assert!(validate_options(&config, &args).is_ok());
+    }
+
+    #[test]
+    fn test_validate_assets_dir_rejects_missing_dirs_and_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("logo.svg");
+        std::fs::write(&file, "<svg />").unwrap();
+
+        assert!(validate_assets_dir(&temp_dir.path().join("missing")).is_err());
+        assert!(validate_assets_dir(&file).is_err());
+        assert!(validate_assets_dir(temp_dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_skip_directory_refuses_directories_with_generated_files() {
+        let (config, args) = resolve(&["aui-next-gen", "my-app", "--skip-directory", "src/app"]);
+        let err = validate_options(&config, &args).unwrap_err().to_string();
+        assert!(err.contains("src/app/layout.tsx"), "{}", err);
+
+        let (config, args) = resolve(&[
+            "aui-next-gen",
+            "my-app",
+            "--skip-directory",
+            "src/fonts/",
+            "--skip-directory",
+            "src/widgets",
+        ]);
+        assert!(validate_options(&config, &args).is_ok());
+        assert_eq!(config.skip_directories, ["src/fonts", "src/widgets"]);
+        assert!(option_warnings(&config)
+            .iter()
+            .any(|warning| warning.message.starts_with("--skip-directory src/widgets")));
+    }
+
+    #[test]
+    fn test_profile_bundles_options_and_no_flags_override_it() {
+        let (config, _) = resolve(&["aui-next-gen", "my-app", "--profile", "team"]);
+        assert!(config.use_husky && config.use_testing && config.ci);
+        assert_eq!(config.formatter, Formatter::EslintPrettier);
+        assert!(!config.dependabot && !config.codeowners);
+        assert!(!config.middleware_chain && !config.strict_install);
+
+        let (config, _) = resolve(&["aui-next-gen", "my-app", "--profile", "team", "--no-husky"]);
+        assert!(!config.use_husky);
+        assert!(config.use_testing && config.ci);
+
+        let (config, _) = resolve(&["aui-next-gen", "my-app", "--profile", "enterprise"]);
+        assert!(config.use_husky && config.use_testing && config.ci);
+        assert!(config.dependabot && config.codeowners);
+        assert!(config.middleware_chain && config.strict_install);
+        let (config, _) = resolve(&[
+            "aui-next-gen",
+            "my-app",
+            "--profile",
+            "enterprise",
+            "--no-ci",
+            "--no-codeowners",
+        ]);
+        assert!(!config.ci && !config.codeowners && config.dependabot);
+        let (config, _) = resolve(&[
+            "aui-next-gen",
+            "my-app",
+            "--profile",
+            "enterprise",
+            "--skip-install",
+        ]);
+        assert!(!config.strict_install);
+
+        let (config, _) = resolve(&["aui-next-gen", "my-app", "--profile", "personal"]);
+        assert!(!config.use_husky && !config.use_testing && !config.ci);
+    }
+
+    #[test]
+    fn test_components_reject_unknown_names() {
+        let result =
+            Cli::try_parse_from(["aui-next-gen", "my-app", "--components", "spinner,modal"]);
+
+        assert!(result.is_err());
+        assert!(result.err().unwrap().to_string().contains("modal"));
+    }
+
+    #[test]
+    fn test_check_output_path_refuses_root_and_top_level_dirs() {
+        let root = check_output_path(Path::new("/"), false);
+        assert!(root.unwrap_err().to_string().contains("filesystem root"));
+
+        let top_level = check_output_path(Path::new("/app"), false);
+        assert!(top_level.unwrap_err().to_string().contains("--force"));
+
+        assert!(check_output_path(Path::new("/tmp/../app"), false).is_err());
+        assert!(check_output_path(Path::new("/"), true).is_ok());
+    }
+
+    #[test]
+    fn test_check_output_path_accepts_relative_project_dir() {
+        assert!(check_output_path(Path::new("my-app"), false).is_ok());
+        assert!(check_output_path(Path::new("./apps/../my-app"), false).is_ok());
+    }
+
+    #[test]
+    fn test_check_output_path_refuses_absurd_depth() {
+        let deep = format!("/work{}", "/nested".repeat(MAX_OUTPUT_DEPTH));
+
+        assert!(check_output_path(Path::new(&deep), false).is_err());
+        assert!(check_output_path(Path::new(&deep), true).is_ok());
+    }
+
+    #[test]
+    fn test_check_path_limits_rejects_long_names_and_deep_paths() {
+        let long_name = "a".repeat(MAX_NAME_BYTES + 1);
+        let err = check_path_limits(Path::new(&long_name)).unwrap_err();
+        assert!(err.to_string().contains("at most 255"));
+
+        let deep = format!(
+            "/work{}/my-app",
+            "/nested-directory".repeat(MAX_PATH_BYTES / 16)
+        );
+        let err = check_path_limits(Path::new(&deep)).unwrap_err();
+        assert!(err.to_string().contains(LONGEST_GENERATED_PATH));
+
+        assert!(check_path_limits(Path::new("my-app")).is_ok());
+    }
+
+    #[test]
+    fn test_validate_options_enforces_max_name_length() {
+        let long_name = "a".repeat(NPM_NAME_MAX_LENGTH + 1);
+        let (config, args) = resolve(&["aui-next-gen", &long_name]);
+        assert!(validate_options(&config, &args).is_err());
+
+        let (config, args) = resolve(&["aui-next-gen", "my-app", "--max-name-length", "4"]);
+        let err = validate_options(&config, &args).unwrap_err();
+        assert!(err.to_string().contains("--max-name-length"));
+    }
+
+    #[test]
+    fn test_install_args_pass_ignore_scripts() {
+        let project = std::path::Path::new("my-app");
+        let (config, _) = resolve(&["aui-next-gen", "my-app"]);
+        assert_eq!(install_args(&config, project), ["install"]);
+
+        let (config, _) = resolve(&["aui-next-gen", "my-app", "--ignore-scripts"]);
+        assert_eq!(
+            install_args(&config, project),
+            ["install", "--ignore-scripts"]
+        );
+    }
+
+    #[test]
+    fn test_install_args_freeze_only_with_a_lockfile() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (config, _) = resolve(&["aui-next-gen", "my-app", "--frozen-lockfile"]);
+        assert_eq!(install_args(&config, temp_dir.path()), ["install"]);
+
+        std::fs::write(temp_dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        assert_eq!(
+            install_args(&config, temp_dir.path()),
+            ["install", "--frozen-lockfile"]
+        );
+
+        let (npm, _) = resolve(&[
+            "aui-next-gen",
+            "my-app",
+            "--frozen-lockfile",
+            "--package-manager",
+            "npm",
+            "--ignore-scripts",
+        ]);
+        assert_eq!(
+            install_args(&npm, temp_dir.path()),
+            ["install", "--ignore-scripts"]
+        );
+        std::fs::write(temp_dir.path().join("package-lock.json"), "{}").unwrap();
+        assert_eq!(
+            install_args(&npm, temp_dir.path()),
+            ["ci", "--ignore-scripts"]
+        );
+    }
+
+    #[test]
+    fn test_frozen_lockfile_warning_only_without_a_lockfile() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (config, _) = resolve(&["aui-next-gen", "my-app", "--frozen-lockfile"]);
+        assert!(frozen_lockfile_warning(&config, temp_dir.path()).is_some());
+
+        std::fs::write(temp_dir.path().join("pnpm-lock.yaml"), "").unwrap();
+        assert!(frozen_lockfile_warning(&config, temp_dir.path()).is_none());
+
+        let (config, _) = resolve(&["aui-next-gen", "my-app"]);
+        assert!(frozen_lockfile_warning(&config, &temp_dir.path().join("new")).is_none());
+    }
+
+    #[test]
+    fn test_validate_tsconfig_extends_accepts_paths_and_packages() {
+        assert!(validate_tsconfig_extends("../../tsconfig.base.json").is_ok());
+        assert!(validate_tsconfig_extends("./tsconfig.shared.json").is_ok());
+        assert!(validate_tsconfig_extends("@acme/tsconfig/nextjs.json").is_ok());
+        assert!(validate_tsconfig_extends("@tsconfig/strictest").is_ok());
+    }
+
+    #[test]
+    fn test_validate_tsconfig_extends_rejects_malformed_targets() {
+        assert!(validate_tsconfig_extends("").is_err());
+        assert!(validate_tsconfig_extends("../base config.json").is_err());
+        assert!(validate_tsconfig_extends("https://example.com/tsconfig.json").is_err());
+        assert!(validate_tsconfig_extends("../../tsconfig.base").is_err());
+    }
 
     #[test]
     fn test_parse_node_version_with_v_prefix() {
@@ -211,6 +1531,26 @@ mod tests {
         assert!(is_node_version_compatible(19, 0)); // Next major
     }
 
+    #[test]
+    fn test_minimum_node_override_rejects_versions_the_default_accepts() {
+        let (_, args) = resolve(&["aui-next-gen", "my-app", "--minimum-node", "20.0"]);
+        let minimum = args.minimum_node.unwrap();
+        assert_eq!(minimum, (20, 0));
+
+        assert!(is_node_version_compatible(18, 20));
+        assert!(!is_node_version_at_least((18, 20), minimum));
+        assert!(is_node_version_at_least((20, 0), minimum));
+        assert!(is_node_version_at_least((22, 1), minimum));
+    }
+
+    #[test]
+    fn test_minimum_node_rejects_malformed_versions() {
+        for value in ["20", "twenty.0", "20.x", ""] {
+            let result = Cli::try_parse_from(["aui-next-gen", "my-app", "--minimum-node", value]);
+            assert!(result.is_err(), "{}", value);
+        }
+    }
+
     #[test]
     fn test_parse_node_version_with_patch() {
         let result = parse_node_version("v18.18.2");
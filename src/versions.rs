@@ -0,0 +1,61 @@
+//! Tool versions shared by the environment checks and the generated files, so
+//! `package.json`, version manager files and validation can't drift apart.
+
+use crate::config::PackageManager;
+
+/// Oldest Node.js release Next.js 15 supports, as `(major, minor)`.
+pub const MIN_NODE_VERSION: (u32, u32) = (18, 18);
+
+/// pnpm release generated projects pin in version manager files.
+pub const PNPM_VERSION: &str = "9.15.0";
+
+pub const NPM_VERSION: &str = "10.9.2";
+
+/// Yarn classic; the generated Dockerfile installs with `--frozen-lockfile`.
+pub const YARN_VERSION: &str = "1.22.22";
+
+pub const BUN_VERSION: &str = "1.1.38";
+
+// npm package ranges shared by package.json and the README's tech stack table
+pub const NEXT_VERSION: &str = "^15.0.0";
+pub const REACT_VERSION: &str = "^19.0.0";
+pub const REACT_18_VERSION: &str = "^18.3.1";
+pub const TYPESCRIPT_VERSION: &str = "^5.0.0";
+pub const TAILWIND_VERSION: &str = "^4.0.0-alpha.31";
+pub const REACT_QUERY_VERSION: &str = "^5.59.0";
+pub const PRISMA_VERSION: &str = "^5.22.0";
+pub const ZOD_VERSION: &str = "^3.23.8";
+pub const T3_ENV_VERSION: &str = "^0.11.1";
+pub const VITEST_VERSION: &str = "^2.1.0";
+pub const HUSKY_VERSION: &str = "^9.1.0";
+pub const TAURI_VERSION: &str = "^2.1.0";
+pub const PINO_VERSION: &str = "^9.5.0";
+pub const POSTCSS_NESTING_VERSION: &str = "^13.0.0";
+pub const BIOME_VERSION: &str = "^1.9.4";
+pub const SENTRY_VERSION: &str = "^8.40.0";
+pub const VERCEL_OTEL_VERSION: &str = "^1.10.0";
+pub const OPENAPI_TYPESCRIPT_VERSION: &str = "^7.8.0";
+
+/// [`MIN_NODE_VERSION`] as a full `major.minor.0` version.
+pub fn min_node_version_string() -> String {
+    format!("{}.{}.0", MIN_NODE_VERSION.0, MIN_NODE_VERSION.1)
+}
+
+/// Release generated projects pin for `package_manager`.
+pub fn package_manager_version(package_manager: PackageManager) -> &'static str {
+    match package_manager {
+        PackageManager::Pnpm => PNPM_VERSION,
+        PackageManager::Npm => NPM_VERSION,
+        PackageManager::Yarn => YARN_VERSION,
+        PackageManager::Bun => BUN_VERSION,
+    }
+}
+
+/// Corepack's `packageManager` value, e.g. `pnpm@9.15.0`.
+pub fn package_manager_spec(package_manager: PackageManager) -> String {
+    format!(
+        "{}@{}",
+        package_manager.command(),
+        package_manager_version(package_manager)
+    )
+}
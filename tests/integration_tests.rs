@@ -19,25 +19,34 @@ fn generate_project_in_path(config: &ProjectConfig, path: &Path) -> anyhow::Resu
     }
 
     // Create files
-    aui_next_generator::create_package_json(path, config)?;
-    aui_next_generator::create_tsconfig(path)?;
-    aui_next_generator::create_postcss_config(path)?;
-    aui_next_generator::create_next_config(path)?;
-    aui_next_generator::create_eslint_config(path)?;
-    aui_next_generator::create_gitignore(path)?;
-    aui_next_generator::create_npmrc(path)?;
-    aui_next_generator::create_app_layout(path, &config.name, config)?;
-    aui_next_generator::create_app_page(path, &config.name)?;
-    aui_next_generator::create_globals_css(path)?;
-    aui_next_generator::create_button_component(path)?;
+    write_files(path, &aui_next_generator::package_json_files(config)?)?;
+    write_files(path, &aui_next_generator::tsconfig_files(config)?)?;
+    write_files(path, &aui_next_generator::postcss_config_files(config)?)?;
+    write_files(path, &aui_next_generator::next_config_files(config)?)?;
+    write_files(path, &aui_next_generator::eslint_config_files(config)?)?;
+    write_files(path, &aui_next_generator::gitignore_files(config)?)?;
+    write_files(path, &aui_next_generator::npmrc_files(config)?)?;
+    write_files(
+        path,
+        &aui_next_generator::app_layout_files(&config.name, config)?,
+    )?;
+    write_files(
+        path,
+        &aui_next_generator::app_page_files(&config.name, config)?,
+    )?;
+    write_files(path, &aui_next_generator::globals_css_files(config)?)?;
+    write_files(path, &aui_next_generator::button_component_files(config)?)?;
 
     if config.use_react_query {
-        aui_next_generator::create_query_provider(path)?;
-        aui_next_generator::create_api_client(path)?;
-        aui_next_generator::create_example_hooks(path)?;
+        write_files(path, &aui_next_generator::query_provider_files(config)?)?;
+        write_files(path, &aui_next_generator::api_client_files(config)?)?;
+        write_files(path, &aui_next_generator::example_hooks_files()?)?;
     }
 
-    aui_next_generator::create_readme(path, &config.name)?;
+    write_files(
+        path,
+        &aui_next_generator::readme_files(&config.name, config)?,
+    )?;
 
     Ok(())
 }
@@ -251,3 +260,451 @@ fn test_project_generation_with_react_query() {
     assert!(hooks_content.contains("usePosts"));
     assert!(hooks_content.contains("useCreatePost"));
 }
+
+#[test]
+fn test_api_test_generated_only_with_testing_and_react_query() {
+    let temp = TempDir::new().unwrap();
+
+    let cases = [
+        ("tested-rq", true, true, true),
+        ("tested-plain", true, false, false),
+        ("untested-rq", false, true, false),
+    ];
+    for (name, testing, react_query, expected) in cases {
+        let config = ProjectConfig::builder(name)
+            .install_deps(false)
+            .react_query(react_query)
+            .testing(testing)
+            .build();
+        generate_project_in(temp.path(), &config).unwrap();
+
+        let api_test = temp.path().join(name).join("src/libs/api.test.ts");
+        assert_eq!(api_test.exists(), expected, "{}", name);
+        assert_eq!(
+            temp.path()
+                .join(name)
+                .join("src/components/Button.test.tsx")
+                .exists(),
+            testing,
+            "{}",
+            name
+        );
+    }
+
+    let content = fs::read_to_string(temp.path().join("tested-rq/src/libs/api.test.ts")).unwrap();
+    assert!(content.contains("ApiError"));
+    assert!(content.contains("vi.stubGlobal('fetch'"));
+}
+
+fn run_generator(args: &[&str], cwd: &Path) -> std::process::Output {
+    std::process::Command::new(env!("CARGO_BIN_EXE_aui-next-gen"))
+        .args(args)
+        .current_dir(cwd)
+        .env_remove("CLICOLOR_FORCE")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn test_force_color_emits_ansi_codes_when_captured() {
+    let temp = TempDir::new().unwrap();
+
+    let forced = run_generator(
+        &["colored-app", "--skip-install", "--force-color"],
+        temp.path(),
+    );
+    assert!(forced.status.success());
+    assert!(String::from_utf8_lossy(&forced.stdout).contains("\x1b["));
+
+    let plain = run_generator(&["plain-app", "--skip-install", "--no-color"], temp.path());
+    assert!(plain.status.success());
+    assert!(!String::from_utf8_lossy(&plain.stdout).contains("\x1b["));
+}
+
+#[test]
+fn test_print_config_prints_toml_without_generating() {
+    let temp = TempDir::new().unwrap();
+
+    let output = run_generator(
+        &[
+            "printed-app",
+            "--skip-install",
+            "--print-config",
+            "--husky",
+            "--port",
+            "4000",
+        ],
+        temp.path(),
+    );
+    assert!(output.status.success());
+
+    let printed = String::from_utf8(output.stdout).unwrap();
+    let config = ProjectConfig::from_toml(&printed).unwrap();
+    assert_eq!(config.name, "printed-app");
+    assert!(config.use_husky && !config.install_deps);
+    assert_eq!(config.port, Some(4000));
+    assert_eq!(config.to_toml().unwrap(), printed);
+    assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_validate_subcommand_reports_without_generating() {
+    let temp = TempDir::new().unwrap();
+    fs::write(
+        temp.path().join("good.toml"),
+        ProjectConfig::builder("good-app")
+            .seo(true)
+            .build()
+            .to_toml()
+            .unwrap(),
+    )
+    .unwrap();
+    fs::write(
+        temp.path().join("bad.toml"),
+        "name = \"/tmp/app\"\nsitemap = true\n",
+    )
+    .unwrap();
+
+    let good = run_generator(&["validate", "good.toml"], temp.path());
+    assert!(good.status.success());
+    assert!(String::from_utf8_lossy(&good.stdout).contains("good.toml is a valid config"));
+
+    let bad = run_generator(&["validate", "bad.toml"], temp.path());
+    assert!(!bad.status.success());
+    let stdout = String::from_utf8_lossy(&bad.stdout);
+    assert!(stdout.contains("❌ 2 errors"));
+    assert!(stdout.contains("unknown key `sitemap`"));
+    assert!(stdout.contains("name: '/tmp/app' must be a relative path"));
+
+    let mut entries: Vec<_> = fs::read_dir(temp.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    entries.sort();
+    assert_eq!(entries, ["bad.toml", "good.toml"]);
+}
+
+#[test]
+fn test_force_color_conflicts_with_no_color() {
+    let temp = TempDir::new().unwrap();
+
+    let output = run_generator(
+        &["both-app", "--skip-install", "--force-color", "--no-color"],
+        temp.path(),
+    );
+    assert!(!output.status.success());
+    assert!(!temp.path().join("both-app").exists());
+}
+
+/// Relative path and content of every file under `root`, sorted by path.
+fn collect_files(root: &Path) -> Vec<(String, Vec<u8>)> {
+    fn walk(root: &Path, dir: &Path, files: &mut Vec<(String, Vec<u8>)>) {
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                walk(root, &path, files);
+            } else {
+                let relative = path.strip_prefix(root).unwrap().display().to_string();
+                files.push((relative, fs::read(&path).unwrap()));
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    walk(root, root, &mut files);
+    files.sort();
+    files
+}
+
+#[test]
+fn test_generation_is_deterministic() {
+    let first = TempDir::new().unwrap();
+    let second = TempDir::new().unwrap();
+    let config = ProjectConfig::builder("deterministic")
+        .install_deps(false)
+        .husky(true)
+        .commitlint(true)
+        .demo_asset(true)
+        .og_route(true)
+        .testing(true)
+        .prisma(true)
+        .with_skeletons(true)
+        .env_validation(EnvValidation::T3)
+        .deploy_target(DeployTarget::Docker)
+        .readme_features(ReadmeFeatures::TechStack)
+        .build();
+
+    generate_project_in(first.path(), &config).unwrap();
+    generate_project_in(second.path(), &config).unwrap();
+
+    let first_files = collect_files(&first.path().join("deterministic"));
+    let second_files = collect_files(&second.path().join("deterministic"));
+    assert!(first_files.len() > 20);
+    assert_eq!(first_files, second_files);
+}
+
+#[test]
+fn test_openapi_spec_generates_api_types() {
+    let temp = TempDir::new().unwrap();
+    let spec_path = temp.path().join("spec.json");
+    fs::write(
+        &spec_path,
+        r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Todos", "version": "1.0.0" },
+  "paths": {},
+  "components": {
+    "schemas": {
+      "Todo": {
+        "type": "object",
+        "required": ["id", "title"],
+        "properties": {
+          "id": { "type": "integer" },
+          "title": { "type": "string" },
+          "done": { "type": "boolean" }
+        }
+      },
+      "TodoList": { "type": "array", "items": { "$ref": "#/components/schemas/Todo" } }
+    }
+  }
+}"##,
+    )
+    .unwrap();
+    let config = ProjectConfig::builder("openapi-app")
+        .install_deps(false)
+        .react_query(true)
+        .openapi(spec_path.display().to_string())
+        .build();
+
+    generate_project_in(temp.path(), &config).unwrap();
+    let project = temp.path().join("openapi-app");
+
+    let types = fs::read_to_string(project.join("src/types/api.d.ts")).unwrap();
+    assert!(types.contains("Todo: { done?: boolean; id: number; title: string }"));
+    assert!(types.contains("export type Todo = components['schemas']['Todo']"));
+    assert!(types.contains("export type TodoList = components['schemas']['TodoList']"));
+    assert!(project.join("openapi.json").exists());
+
+    let package_json = fs::read_to_string(project.join("package.json")).unwrap();
+    assert!(package_json
+        .contains("\"api:types\": \"openapi-typescript ./openapi.json -o src/types/api.d.ts\""));
+    assert!(package_json.contains("\"openapi-typescript\""));
+
+    let api = fs::read_to_string(project.join("src/libs/api.ts")).unwrap();
+    assert!(api.contains("import type { components } from '@/types/api'"));
+    assert!(api.contains("export type Schema<Name extends keyof components['schemas']>"));
+}
+
+#[test]
+fn test_babelrc_only_with_use_babel() {
+    let temp = TempDir::new().unwrap();
+    let swc = ProjectConfig::builder("swc-app")
+        .install_deps(false)
+        .build();
+    let babel = ProjectConfig::builder("babel-app")
+        .install_deps(false)
+        .babel(true)
+        .build();
+
+    generate_project_in(temp.path(), &swc).unwrap();
+    generate_project_in(temp.path(), &babel).unwrap();
+
+    assert!(!temp.path().join("swc-app/.babelrc").exists());
+    let babelrc: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(temp.path().join("babel-app/.babelrc")).unwrap())
+            .unwrap();
+    assert_eq!(babelrc["presets"], serde_json::json!(["next/babel"]));
+
+    // next/font needs SWC, so the Babel layout must not use it
+    let layout = fs::read_to_string(temp.path().join("babel-app/src/app/layout.tsx")).unwrap();
+    assert!(!layout.contains("next/font"));
+    assert!(layout.contains("<body>"));
+    let readme = fs::read_to_string(temp.path().join("babel-app/README.md")).unwrap();
+    assert!(readme.contains("## Babel"));
+}
+
+#[test]
+fn test_longest_generated_path_covers_every_file() {
+    let temp = TempDir::new().unwrap();
+    let config = ProjectConfig::builder("longest")
+        .install_deps(false)
+        .components_barrel(true)
+        .styling(Styling::CssModules)
+        .component(Component::Spinner)
+        .testing(true)
+        .react_query(true)
+        .with_skeletons(true)
+        .desktop(Desktop::Tauri)
+        .husky(true)
+        .commitlint(true)
+        .prisma(true)
+        .ratelimit(true)
+        .seo(true)
+        .logger(Logger::Console)
+        .vscode(true)
+        .env_validation(EnvValidation::Zod)
+        .build();
+
+    generate_project_in(temp.path(), &config).unwrap();
+
+    for (path, _) in collect_files(&temp.path().join("longest")) {
+        assert!(
+            path.len() <= LONGEST_GENERATED_PATH.len(),
+            "{} is longer than LONGEST_GENERATED_PATH",
+            path
+        );
+    }
+}
+
+#[test]
+fn test_sitemap_lists_exactly_the_generated_pages() {
+    let temp = TempDir::new().unwrap();
+    let config = ProjectConfig::builder("seo-app")
+        .install_deps(false)
+        .seo(true)
+        .og_route(true)
+        .with_skeletons(true)
+        .build();
+
+    generate_project_in(temp.path(), &config).unwrap();
+    let project = temp.path().join("seo-app");
+
+    // Every src/app/**/page.tsx is a public route; route handlers aren't
+    let mut pages: Vec<String> = collect_files(&project.join("src/app"))
+        .into_iter()
+        .filter_map(|(path, _)| path.strip_suffix("page.tsx").map(|dir| format!("/{}", dir)))
+        .map(|route| match route.trim_end_matches('/') {
+            "" => "/".to_string(),
+            trimmed => trimmed.to_string(),
+        })
+        .collect();
+    pages.sort();
+    assert_eq!(pages, public_routes(&config));
+
+    let sitemap = fs::read_to_string(project.join("src/app/sitemap.ts")).unwrap();
+    assert_eq!(sitemap.matches("url: `${siteUrl}").count(), pages.len());
+    assert!(sitemap.contains("url: `${siteUrl}`,"));
+    assert!(!sitemap.contains("/api/og"));
+    assert!(fs::read_to_string(project.join("src/app/robots.ts"))
+        .unwrap()
+        .contains("sitemap: `${siteUrl}/sitemap.xml`"));
+}
+
+/// Gives spawned git processes an identity so commits work on CI machines
+/// without a global git config.
+fn set_git_identity() {
+    std::env::set_var("GIT_AUTHOR_NAME", "AUI Test");
+    std::env::set_var("GIT_AUTHOR_EMAIL", "test@example.com");
+    std::env::set_var("GIT_COMMITTER_NAME", "AUI Test");
+    std::env::set_var("GIT_COMMITTER_EMAIL", "test@example.com");
+}
+
+#[test]
+fn test_granular_commits_create_one_commit_per_feature() {
+    if !is_git_available() {
+        return;
+    }
+    set_git_identity();
+
+    let temp = TempDir::new().unwrap();
+    let mut config = create_test_config("granular-test", false, true);
+    config.init_git = true;
+    config.granular_commits = true;
+
+    let result = generate_project_in(temp.path(), &config);
+    assert!(result.is_ok(), "{:?}", result.err());
+
+    let output = std::process::Command::new("git")
+        .args(["log", "--reverse", "--format=%s"])
+        .current_dir(temp.path().join(&config.name))
+        .output()
+        .unwrap();
+    let log = String::from_utf8_lossy(&output.stdout);
+    let messages: Vec<&str> = log.lines().collect();
+
+    assert_eq!(messages.len(), 3);
+    assert!(messages[0].contains("scaffold"));
+    assert!(messages[1].contains("Tailwind"));
+    assert!(messages[2].contains("React Query"));
+}
+
+#[test]
+fn test_git_remote_is_added_after_initial_commit() {
+    if !is_git_available() {
+        return;
+    }
+    set_git_identity();
+
+    let temp = TempDir::new().unwrap();
+    let url = "git@github.com:acme/remote-test.git";
+    let config = ProjectConfig::builder("remote-test")
+        .install_deps(false)
+        .git(true)
+        .git_remote(url)
+        .build();
+
+    let result = generate_project_in(temp.path(), &config);
+    assert!(result.is_ok(), "{:?}", result.err());
+
+    let output = std::process::Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(temp.path().join(&config.name))
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), url);
+
+    let steps = next_steps(&config);
+    assert!(steps.contains(&"git push -u origin main".to_string()));
+}
+
+#[test]
+fn test_git_branch_names_the_initial_branch() {
+    if !is_git_available() {
+        return;
+    }
+    set_git_identity();
+
+    let temp = TempDir::new().unwrap();
+    let config = ProjectConfig::builder("branch-test")
+        .install_deps(false)
+        .git(true)
+        .git_branch("trunk")
+        .git_remote("git@github.com:acme/branch-test.git")
+        .build();
+
+    let result = generate_project_in(temp.path(), &config);
+    assert!(result.is_ok(), "{:?}", result.err());
+
+    let output = std::process::Command::new("git")
+        .args(["branch", "--show-current"])
+        .current_dir(temp.path().join(&config.name))
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "trunk");
+    assert!(next_steps(&config).contains(&"git push -u origin trunk".to_string()));
+}
+
+#[test]
+fn test_upgrade_refreshes_config_and_preserves_sources() {
+    let temp = TempDir::new().unwrap();
+    let config = create_test_config("upgrade-test", false, false);
+    let project_path = temp.path().join(&config.name);
+    fs::create_dir(&project_path).unwrap();
+    generate_project_in_path(&config, &project_path).unwrap();
+
+    let stale_tsconfig = r#"{ "compilerOptions": { "target": "es3" } }"#;
+    let custom_page = "export default function Home() { return <p>mine</p> }\n";
+    fs::write(project_path.join("tsconfig.json"), stale_tsconfig).unwrap();
+    fs::write(project_path.join("src/app/page.tsx"), custom_page).unwrap();
+
+    let results = upgrade_project(&project_path).unwrap();
+    assert!(results.contains(&("tsconfig.json", UpgradeStatus::Updated)));
+
+    let tsconfig = fs::read_to_string(project_path.join("tsconfig.json")).unwrap();
+    assert_eq!(tsconfig, tsconfig_content(&config));
+    let backup = fs::read_to_string(project_path.join("tsconfig.json.bak")).unwrap();
+    assert_eq!(backup, stale_tsconfig);
+
+    let page = fs::read_to_string(project_path.join("src/app/page.tsx")).unwrap();
+    assert_eq!(page, custom_page);
+}